@@ -154,10 +154,20 @@ pub mod pallet {
 		#[pallet::constant]
 		type KickPenaltySessionLength: Get<u32>;
 
-		/// Will be kicked if block is not produced in threshold.
+		/// Will be kicked if block is not produced in threshold. The initial value of
+		/// `KickThreshold`, which the `UpdateOrigin` can change.
 		#[pallet::constant]
 		type CollatorKickThreshold: Get<Permill>;
 
+		/// Maximum number of kicked candidates kept in `RecentlyKicked`.
+		#[pallet::constant]
+		type MaxRecentlyKicked: Get<u32>;
+
+		/// The number of sessions after `register_as_candidate` during which the candidate is not
+		/// rewarded for authoring blocks.
+		#[pallet::constant]
+		type RewardGracePeriod: Get<SessionIndex>;
+
 		/// The weight information of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -195,6 +205,14 @@ pub mod pallet {
 	#[pallet::getter(fn candidacy_bond)]
 	pub type CandidacyBond<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	/// Maximum candidacy bond, to limit the influence a single candidate can gain by bonding.
+	/// Unlimited if not set.
+	///
+	/// MaxCandidacyBond: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn max_candidacy_bond)]
+	pub type MaxCandidacyBond<T> = StorageValue<_, BalanceOf<T>, OptionQuery>;
+
 	/// Session points for each candidate.
 	///
 	/// SessionPoints: map AccountId => u32
@@ -209,6 +227,36 @@ pub mod pallet {
 	#[pallet::getter(fn non_candidates)]
 	pub type NonCandidates<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, ValueQuery>;
 
+	/// Mapping from the candidates in their reward grace period to the session index at which
+	/// they become eligible for rewards.
+	///
+	/// RewardEligibleSession: map AccountId => SessionIndex
+	#[pallet::storage]
+	#[pallet::getter(fn reward_eligible_session)]
+	pub type RewardEligibleSession<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultKickThreshold<T: Config>() -> Permill {
+		T::CollatorKickThreshold::get()
+	}
+
+	/// Candidates with no more than this share of the average session points are kicked at the
+	/// end of the session.
+	///
+	/// KickThreshold: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn kick_threshold)]
+	pub type KickThreshold<T: Config> = StorageValue<_, Permill, ValueQuery, DefaultKickThreshold<T>>;
+
+	/// The most recently kicked candidates, and the session they were kicked at the end of,
+	/// oldest first.
+	///
+	/// RecentlyKicked: Vec<(AccountId, SessionIndex)>
+	#[pallet::storage]
+	#[pallet::getter(fn recently_kicked)]
+	pub type RecentlyKicked<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, SessionIndex), T::MaxRecentlyKicked>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub invulnerables: Vec<T::AccountId>,
@@ -263,10 +311,22 @@ pub mod pallet {
 		NewDesiredCandidates(u32),
 		/// Candidacy bond was updated. \[new_candidacy_bond\]
 		NewCandidacyBond(BalanceOf<T>),
+		/// Maximum candidacy bond was updated. \[new_max_candidacy_bond\]
+		NewMaxCandidacyBond(Option<BalanceOf<T>>),
 		/// A candidate was added. \[who, bond\]
 		CandidateAdded(T::AccountId, BalanceOf<T>),
 		/// A candidate was removed. \[who\]
 		CandidateRemoved(T::AccountId),
+		/// An invulnerable was added. \[who\]
+		InvulnerableAdded(T::AccountId),
+		/// An invulnerable was removed. \[who\]
+		InvulnerableRemoved(T::AccountId),
+		/// A candidate's reward grace period has ended. \[who\]
+		CandidateRewardEligible(T::AccountId),
+		/// Kick threshold was updated. \[new_kick_threshold\]
+		KickThresholdUpdated(Permill),
+		/// A candidate was kicked at the end of a session. \[who, session\]
+		CandidateKicked(T::AccountId, SessionIndex),
 	}
 
 	// Errors inform users that something went wrong.
@@ -285,6 +345,10 @@ pub mod pallet {
 		AlreadyInvulnerable,
 		InvalidProof,
 		MaxInvulnerablesExceeded,
+		NotInvulnerable,
+		CandidacyBondNotMet,
+		BondExceedsMaximum,
+		MaxBelowCandidacyBond,
 	}
 
 	#[pallet::hooks]
@@ -302,6 +366,32 @@ pub mod pallet {
 			Ok(())
 		}
 
+		#[pallet::weight(T::WeightInfo::add_invulnerable(T::MaxInvulnerables::get()))]
+		pub fn add_invulnerable(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let invulnerables_len = <Invulnerables<T>>::try_mutate(|invulnerables| -> Result<usize, DispatchError> {
+				ensure!(!invulnerables.contains(&who), Error::<T>::AlreadyInvulnerable);
+				invulnerables
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::MaxInvulnerablesExceeded)?;
+				Ok(invulnerables.len())
+			})?;
+			Self::deposit_event(Event::InvulnerableAdded(who));
+			Ok(Some(T::WeightInfo::add_invulnerable(invulnerables_len as u32)).into())
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_invulnerable(T::MaxInvulnerables::get()))]
+		pub fn remove_invulnerable(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let invulnerables_len = <Invulnerables<T>>::try_mutate(|invulnerables| -> Result<usize, DispatchError> {
+				ensure!(invulnerables.contains(&who), Error::<T>::NotInvulnerable);
+				invulnerables.retain(|x| *x != who);
+				Ok(invulnerables.len())
+			})?;
+			Self::deposit_event(Event::InvulnerableRemoved(who));
+			Ok(Some(T::WeightInfo::remove_invulnerable(invulnerables_len as u32)).into())
+		}
+
 		#[pallet::weight(T::WeightInfo::set_desired_candidates())]
 		pub fn set_desired_candidates(origin: OriginFor<T>, max: u32) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
@@ -316,12 +406,37 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::set_candidacy_bond())]
 		pub fn set_candidacy_bond(origin: OriginFor<T>, bond: BalanceOf<T>) -> DispatchResult {
 			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_within_max_bond(bond)?;
 			<CandidacyBond<T>>::put(&bond);
 			Self::deposit_event(Event::NewCandidacyBond(bond));
 			Ok(())
 		}
 
-		#[pallet::weight(T::WeightInfo::register_as_candidate(T::MaxCandidates::get()))]
+		/// Sets the maximum candidacy bond, or removes it if `None`. The maximum cannot be below
+		/// the current candidacy bond.
+		#[pallet::weight(T::WeightInfo::set_max_candidacy_bond())]
+		pub fn set_max_candidacy_bond(origin: OriginFor<T>, max: Option<BalanceOf<T>>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(max) = max {
+				ensure!(max >= Self::candidacy_bond(), Error::<T>::MaxBelowCandidacyBond);
+			}
+			<MaxCandidacyBond<T>>::set(max);
+			Self::deposit_event(Event::NewMaxCandidacyBond(max));
+			Ok(())
+		}
+
+		/// Sets the share of the average session points below which candidates are kicked.
+		#[pallet::weight(T::WeightInfo::set_kick_threshold())]
+		pub fn set_kick_threshold(origin: OriginFor<T>, threshold: Permill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			<KickThreshold<T>>::put(threshold);
+			Self::deposit_event(Event::KickThresholdUpdated(threshold));
+			Ok(())
+		}
+
+		/// Registers the caller as a candidate, reserving the `CandidacyBond` from them.
+		#[pallet::weight(T::WeightInfo::register_as_candidate(T::MaxCandidates::get())
+			.saturating_add(T::DbWeight::get().writes(1)))]
 		pub fn register_as_candidate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
@@ -334,29 +449,50 @@ pub mod pallet {
 
 			let deposit = Self::candidacy_bond();
 			let bounded_candidates_len = Self::do_register_candidate(&who, deposit)?;
+			let grace_period = T::RewardGracePeriod::get();
+			if !grace_period.is_zero() {
+				<RewardEligibleSession<T>>::insert(&who, T::ValidatorSet::session_index().saturating_add(grace_period));
+			}
 			Self::deposit_event(Event::CandidateAdded(who, deposit));
 
-			Ok(Some(T::WeightInfo::register_as_candidate(bounded_candidates_len as u32)).into())
+			// `RewardEligibleSession` is not covered by the benchmarks yet, so it is charged here.
+			Ok(Some(
+				T::WeightInfo::register_as_candidate(bounded_candidates_len as u32)
+					.saturating_add(T::DbWeight::get().writes(1)),
+			)
+			.into())
 		}
 
+		/// Registers `new_candidate` as a candidate on behalf of governance. If `bonded`, the
+		/// `CandidacyBond` is reserved from the candidate as for `register_as_candidate`,
+		/// otherwise the candidate is added without a bond.
 		#[pallet::weight(T::WeightInfo::register_candidate(T::MaxCandidates::get()))]
-		pub fn register_candidate(origin: OriginFor<T>, new_candidate: T::AccountId) -> DispatchResultWithPostInfo {
+		pub fn register_candidate(
+			origin: OriginFor<T>,
+			new_candidate: T::AccountId,
+			bonded: bool,
+		) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 
-			let bounded_candidates_len = Self::do_register_candidate(&new_candidate, Zero::zero())?;
+			let deposit = if bonded { Self::candidacy_bond() } else { Zero::zero() };
+			let bounded_candidates_len = Self::do_register_candidate(&new_candidate, deposit)?;
 
-			Self::deposit_event(Event::CandidateAdded(new_candidate, Zero::zero()));
+			Self::deposit_event(Event::CandidateAdded(new_candidate, deposit));
 			Ok(Some(T::WeightInfo::register_candidate(bounded_candidates_len as u32)).into())
 		}
 
-		#[pallet::weight(T::WeightInfo::leave_intent(T::MaxCandidates::get()))]
+		#[pallet::weight(T::WeightInfo::leave_intent(T::MaxCandidates::get())
+			.saturating_add(T::DbWeight::get().writes(1)))]
 		pub fn leave_intent(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let current_count = Self::try_remove_candidate(&who)?;
 			<NonCandidates<T>>::insert(who, T::ValidatorSet::session_index().saturating_add(SESSION_DELAY));
 
-			Ok(Some(T::WeightInfo::leave_intent(current_count as u32)).into())
+			Ok(
+				Some(T::WeightInfo::leave_intent(current_count as u32).saturating_add(T::DbWeight::get().writes(1)))
+					.into(),
+			)
 		}
 
 		#[pallet::weight(T::WeightInfo::withdraw_bond())]
@@ -381,6 +517,13 @@ pub mod pallet {
 			T::PotId::get().into_account()
 		}
 
+		fn ensure_within_max_bond(bond: BalanceOf<T>) -> DispatchResult {
+			if let Some(max) = Self::max_candidacy_bond() {
+				ensure!(bond <= max, Error::<T>::BondExceedsMaximum);
+			}
+			Ok(())
+		}
+
 		/// Removes a candidate if they exist and sends them back their deposit
 		fn try_remove_candidate(who: &T::AccountId) -> Result<usize, DispatchError> {
 			let current_count = <Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
@@ -393,6 +536,7 @@ pub mod pallet {
 				candidates.take(who).ok_or(Error::<T>::NotCandidate)?;
 				Ok(candidates.len())
 			})?;
+			<RewardEligibleSession<T>>::remove(who);
 			Self::deposit_event(Event::CandidateRemoved(who.clone()));
 			Ok(current_count)
 		}
@@ -415,6 +559,11 @@ pub mod pallet {
 			);
 			ensure!(!Self::invulnerables().contains(&who), Error::<T>::AlreadyInvulnerable);
 			ensure!(T::ValidatorSet::is_registered(&who), Error::<T>::RequireSessionKey);
+			Self::ensure_within_max_bond(deposit)?;
+			ensure!(
+				deposit.is_zero() || T::Currency::can_reserve(&who, deposit),
+				Error::<T>::CandidacyBondNotMet
+			);
 
 			<Candidates<T>>::try_mutate(|candidates| -> Result<usize, DispatchError> {
 				ensure!(!candidates.contains(who), Error::<T>::AlreadyCandidate);
@@ -440,22 +589,25 @@ pub mod pallet {
 				author,
 				<frame_system::Pallet<T>>::block_number(),
 			);
-			let pot = Self::account_id();
-			// assumes an ED will be sent to pot.
-			let reward = T::Currency::free_balance(&pot)
-				.checked_sub(&T::Currency::minimum_balance())
-				.unwrap_or_default()
-				.div(2u32.into());
-			// `reward` is half of pot account minus ED, this should never fail.
-			let _success = T::Currency::transfer(&pot, &author, reward, KeepAlive);
-			debug_assert!(_success.is_ok());
+			// candidates in their reward grace period are not rewarded.
+			if !<RewardEligibleSession<T>>::contains_key(&author) {
+				let pot = Self::account_id();
+				// assumes an ED will be sent to pot.
+				let reward = T::Currency::free_balance(&pot)
+					.checked_sub(&T::Currency::minimum_balance())
+					.unwrap_or_default()
+					.div(2u32.into());
+				// `reward` is half of pot account minus ED, this should never fail.
+				let _success = T::Currency::transfer(&pot, &author, reward, KeepAlive);
+				debug_assert!(_success.is_ok());
+			}
 
 			if <SessionPoints<T>>::contains_key(&author) {
 				<SessionPoints<T>>::mutate(author, |point| *point += POINT_PER_BLOCK);
 			}
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::note_author(),
+				T::WeightInfo::note_author().saturating_add(T::DbWeight::get().reads(1)),
 				DispatchClass::Mandatory,
 			);
 		}
@@ -489,12 +641,18 @@ pub mod pallet {
 			let validators = T::ValidatorSet::validators();
 			let candidates = Self::candidates();
 			let mut collators = vec![];
+			let mut newly_eligible: Weight = 0;
 
 			candidates.iter().for_each(|candidate| {
 				if validators.contains(&candidate) {
 					collators.push(candidate);
 					<SessionPoints<T>>::insert(&candidate, 0);
 				}
+				if Self::reward_eligible_session(&candidate).map_or(false, |eligible| index >= eligible) {
+					<RewardEligibleSession<T>>::remove(&candidate);
+					newly_eligible += 1;
+					Self::deposit_event(Event::CandidateRewardEligible(candidate.clone()));
+				}
 			});
 
 			log::debug!(
@@ -505,8 +663,10 @@ pub mod pallet {
 				collators
 			);
 
+			// Plus a `RewardEligibleSession` read per candidate, and a removal per newly eligible one.
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::start_session(candidates.len() as u32, collators.len() as u32),
+				T::WeightInfo::start_session(candidates.len() as u32, collators.len() as u32)
+					.saturating_add(T::DbWeight::get().reads_writes(candidates.len() as Weight, newly_eligible)),
 				DispatchClass::Mandatory,
 			);
 		}
@@ -521,7 +681,7 @@ pub mod pallet {
 				sum
 			});
 			let average_session_point: u32 = total_session_point.checked_div(candidates_len).unwrap_or_default();
-			let required_point: u32 = T::CollatorKickThreshold::get().mul_floor(average_session_point);
+			let required_point: u32 = Self::kick_threshold().mul_floor(average_session_point);
 			for (who, point) in session_points {
 				// required_point maybe is zero
 				if point <= required_point {
@@ -544,15 +704,27 @@ pub mod pallet {
 						debug_assert!(false, "failed to remove candidate {:?}", why);
 					} else {
 						<NonCandidates<T>>::insert(
-							who,
+							&who,
 							T::ValidatorSet::session_index().saturating_add(T::KickPenaltySessionLength::get()),
 						);
+						<RecentlyKicked<T>>::mutate(|kicked| {
+							// drop the oldest entry to make room
+							if kicked.len() as u32 >= T::MaxRecentlyKicked::get() && !kicked.is_empty() {
+								kicked.remove(0);
+							}
+							let _ = kicked.try_push((who.clone(), index));
+						});
+						Self::deposit_event(Event::CandidateKicked(who, index));
 					}
 				}
 			}
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::end_session(candidates_len, removed_len as u32),
+				T::WeightInfo::end_session(candidates_len, removed_len as u32)
+					.saturating_add(T::DbWeight::get().writes(removed_len as Weight))
+					// The `KickThreshold` read, and a `RecentlyKicked` update per kicked candidate.
+					.saturating_add(T::DbWeight::get().reads(1))
+					.saturating_add(T::DbWeight::get().reads_writes(removed_len as Weight, removed_len as Weight)),
 				DispatchClass::Mandatory,
 			);
 		}
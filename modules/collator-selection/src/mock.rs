@@ -196,6 +196,8 @@ parameter_types! {
 	pub const MaxInvulnerables: u32 = 4;
 	pub const KickPenaltySessionLength: u32 = 8;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(100);
+	pub static RewardGracePeriod: u32 = 0;
+	pub const MaxRecentlyKicked: u32 = 2;
 }
 
 impl Config for Test {
@@ -209,6 +211,8 @@ impl Config for Test {
 	type MaxInvulnerables = MaxInvulnerables;
 	type KickPenaltySessionLength = KickPenaltySessionLength;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type RewardGracePeriod = RewardGracePeriod;
+	type MaxRecentlyKicked = MaxRecentlyKicked;
 	type WeightInfo = ();
 }
 
@@ -17,14 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate as collator_selection;
-use crate::{mock::*, Error, NonCandidates, RESERVE_ID};
+use crate::{mock::*, Error, NonCandidates, SessionPoints, RESERVE_ID};
 use frame_support::{
 	assert_noop, assert_ok,
 	storage::bounded_btree_set::BoundedBTreeSet,
 	traits::{Currency, GenesisBuild, NamedReservableCurrency, OnInitialize},
 };
-use pallet_balances::Error as BalancesError;
-use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin};
+use pallet_session::SessionManager;
+use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin, Permill};
 
 #[test]
 fn basic_setup_works() {
@@ -55,6 +55,57 @@ fn it_should_set_invulnerables() {
 	});
 }
 
+#[test]
+fn add_and_remove_invulnerable_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2]);
+
+		assert_ok!(CollatorSelection::add_invulnerable(
+			Origin::signed(RootAccount::get()),
+			3
+		));
+		assert_eq!(CollatorSelection::invulnerables(), vec![1, 2, 3]);
+		System::assert_last_event(Event::CollatorSelection(collator_selection::Event::InvulnerableAdded(
+			3,
+		)));
+
+		// cannot add duplicates.
+		assert_noop!(
+			CollatorSelection::add_invulnerable(Origin::signed(RootAccount::get()), 3),
+			Error::<Test>::AlreadyInvulnerable
+		);
+
+		// cannot exceed MaxInvulnerables.
+		assert_ok!(CollatorSelection::add_invulnerable(
+			Origin::signed(RootAccount::get()),
+			4
+		));
+		assert_noop!(
+			CollatorSelection::add_invulnerable(Origin::signed(RootAccount::get()), 5),
+			Error::<Test>::MaxInvulnerablesExceeded
+		);
+
+		assert_ok!(CollatorSelection::remove_invulnerable(
+			Origin::signed(RootAccount::get()),
+			2
+		));
+		assert_eq!(CollatorSelection::invulnerables(), vec![1, 3, 4]);
+		System::assert_last_event(Event::CollatorSelection(
+			collator_selection::Event::InvulnerableRemoved(2),
+		));
+
+		assert_noop!(
+			CollatorSelection::remove_invulnerable(Origin::signed(RootAccount::get()), 2),
+			Error::<Test>::NotInvulnerable
+		);
+
+		// rejects bad origin
+		assert_noop!(CollatorSelection::add_invulnerable(Origin::signed(1), 5), BadOrigin);
+		assert_noop!(CollatorSelection::remove_invulnerable(Origin::signed(1), 1), BadOrigin);
+	});
+}
+
 #[test]
 fn set_desired_candidates_works() {
 	new_test_ext().execute_with(|| {
@@ -99,6 +150,71 @@ fn set_candidacy_bond() {
 	});
 }
 
+#[test]
+fn max_candidacy_bond_is_enforced() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CollatorSelection::max_candidacy_bond(), None);
+
+		// rejects bad origin.
+		assert_noop!(
+			CollatorSelection::set_max_candidacy_bond(Origin::signed(1), Some(20)),
+			BadOrigin
+		);
+		assert_ok!(CollatorSelection::set_max_candidacy_bond(
+			Origin::signed(RootAccount::get()),
+			Some(20)
+		));
+		System::assert_last_event(Event::CollatorSelection(
+			collator_selection::Event::NewMaxCandidacyBond(Some(20)),
+		));
+
+		// the bond can be set up to the maximum, but not above it.
+		assert_ok!(CollatorSelection::set_candidacy_bond(
+			Origin::signed(RootAccount::get()),
+			20
+		));
+		assert_noop!(
+			CollatorSelection::set_candidacy_bond(Origin::signed(RootAccount::get()), 21),
+			Error::<Test>::BondExceedsMaximum
+		);
+
+		assert_ok!(Session::set_keys(
+			Origin::signed(3),
+			MockSessionKeys {
+				aura: UintAuthorityId(3)
+			},
+			vec![]
+		));
+		assert_ok!(Session::set_keys(
+			Origin::signed(4),
+			MockSessionKeys {
+				aura: UintAuthorityId(4)
+			},
+			vec![]
+		));
+
+		// registering at the maximum works.
+		assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(3)));
+		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &3), 20);
+
+		// the maximum cannot be lowered below the current bond, only after it.
+		assert_noop!(
+			CollatorSelection::set_max_candidacy_bond(Origin::signed(RootAccount::get()), Some(15)),
+			Error::<Test>::MaxBelowCandidacyBond
+		);
+		assert_ok!(CollatorSelection::set_candidacy_bond(
+			Origin::signed(RootAccount::get()),
+			15
+		));
+		assert_ok!(CollatorSelection::set_max_candidacy_bond(
+			Origin::signed(RootAccount::get()),
+			Some(15)
+		));
+		assert_eq!(CollatorSelection::max_candidacy_bond(), Some(15));
+	});
+}
+
 #[test]
 fn cannot_register_candidate_if_too_many() {
 	new_test_ext().execute_with(|| {
@@ -195,7 +311,7 @@ fn cannot_register_as_candidate_if_poor() {
 		));
 		assert_noop!(
 			CollatorSelection::register_as_candidate(Origin::signed(33)),
-			BalancesError::<Test>::InsufficientBalance,
+			Error::<Test>::CandidacyBondNotMet,
 		);
 	});
 }
@@ -256,15 +372,49 @@ fn register_candidate_works() {
 			},
 			vec![]
 		));
+		// a bonded registration still requires the bond.
+		assert_noop!(
+			CollatorSelection::register_candidate(Origin::signed(RootAccount::get()), 33, true),
+			Error::<Test>::CandidacyBondNotMet,
+		);
 		assert_ok!(CollatorSelection::register_candidate(
 			Origin::signed(RootAccount::get()),
-			33
+			33,
+			false
 		));
 		assert_eq!(Balances::free_balance(&33), 5);
 		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &33), 0);
 	});
 }
 
+#[test]
+fn register_candidate_with_bond_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Session::set_keys(
+			Origin::signed(3),
+			MockSessionKeys {
+				aura: UintAuthorityId(3)
+			},
+			vec![]
+		));
+		assert_noop!(
+			CollatorSelection::register_candidate(Origin::signed(3), 3, true),
+			BadOrigin
+		);
+		assert_ok!(CollatorSelection::register_candidate(
+			Origin::signed(RootAccount::get()),
+			3,
+			true
+		));
+		System::assert_last_event(Event::CollatorSelection(collator_selection::Event::CandidateAdded(
+			3, 10,
+		)));
+		assert_eq!(Balances::free_balance(&3), 90);
+		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &3), 10);
+	});
+}
+
 #[test]
 fn leave_intent() {
 	new_test_ext().execute_with(|| {
@@ -419,6 +569,39 @@ fn fees_edgecases() {
 	});
 }
 
+#[test]
+fn reward_grace_period_works() {
+	new_test_ext().execute_with(|| {
+		RewardGracePeriod::set(1);
+		Balances::make_free_balance_be(&CollatorSelection::account_id(), 105);
+		assert_ok!(Session::set_keys(
+			Origin::signed(4),
+			MockSessionKeys {
+				aura: UintAuthorityId(4)
+			},
+			vec![]
+		));
+		assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(4)));
+		assert_eq!(CollatorSelection::reward_eligible_session(4), Some(1));
+		assert_eq!(Balances::free_balance(4), 90);
+
+		// 4 is the default author, and is not rewarded during the grace period.
+		initialize_to_block(Period::get() - 1);
+		assert_eq!(Balances::free_balance(4), 90);
+		assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 105);
+
+		// becomes eligible at the start of session 1.
+		initialize_to_block(Period::get() + 1);
+		assert_eq!(CollatorSelection::reward_eligible_session(4), None);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event
+				== Event::CollatorSelection(collator_selection::Event::CandidateRewardEligible(4))));
+		assert!(Balances::free_balance(4) > 90);
+		assert!(Balances::free_balance(CollatorSelection::account_id()) < 105);
+	});
+}
+
 #[test]
 fn session_management_works() {
 	new_test_ext().execute_with(|| {
@@ -512,6 +695,60 @@ fn kick_mechanism() {
 	});
 }
 
+#[test]
+fn kick_threshold_can_be_updated() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CollatorSelection::kick_threshold(), CollatorKickThreshold::get());
+
+		// rejects bad origin.
+		assert_noop!(
+			CollatorSelection::set_kick_threshold(Origin::signed(1), Permill::from_percent(50)),
+			BadOrigin
+		);
+		assert_ok!(CollatorSelection::set_kick_threshold(
+			Origin::signed(RootAccount::get()),
+			Permill::from_percent(50)
+		));
+		System::assert_last_event(Event::CollatorSelection(
+			collator_selection::Event::KickThresholdUpdated(Permill::from_percent(50)),
+		));
+
+		for who in 3..=4 {
+			assert_ok!(Session::set_keys(
+				Origin::signed(who),
+				MockSessionKeys {
+					aura: UintAuthorityId(who)
+				},
+				vec![]
+			));
+			assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(who)));
+		}
+
+		// the average is 50 points, so 25 are required.
+		SessionPoints::<Test>::insert(3, 40);
+		SessionPoints::<Test>::insert(4, 60);
+		CollatorSelection::end_session(1);
+		assert_eq!(CollatorSelection::candidates().len(), 2);
+		assert!(CollatorSelection::recently_kicked().is_empty());
+
+		// at 80%, 40 points are required.
+		assert_ok!(CollatorSelection::set_kick_threshold(
+			Origin::signed(RootAccount::get()),
+			Permill::from_percent(80)
+		));
+		SessionPoints::<Test>::insert(3, 40);
+		SessionPoints::<Test>::insert(4, 60);
+		CollatorSelection::end_session(2);
+		assert_eq!(CollatorSelection::candidates().len(), 1);
+		assert!(!CollatorSelection::candidates().contains(&3));
+		assert_eq!(CollatorSelection::recently_kicked().into_inner(), vec![(3, 2)]);
+		System::assert_last_event(Event::CollatorSelection(collator_selection::Event::CandidateKicked(
+			3, 2,
+		)));
+	});
+}
+
 #[test]
 fn exceeding_max_invulnerables_should_fail() {
 	new_test_ext().execute_with(|| {
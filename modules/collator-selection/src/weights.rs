@@ -48,8 +48,12 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for module_collator_selection.
 pub trait WeightInfo {
 	fn set_invulnerables(b: u32, ) -> Weight;
+	fn add_invulnerable(b: u32, ) -> Weight;
+	fn remove_invulnerable(b: u32, ) -> Weight;
 	fn set_desired_candidates() -> Weight;
 	fn set_candidacy_bond() -> Weight;
+	fn set_max_candidacy_bond() -> Weight;
+	fn set_kick_threshold() -> Weight;
 	fn register_as_candidate(c: u32, ) -> Weight;
 	fn register_candidate(c: u32, ) -> Weight;
 	fn leave_intent(c: u32, ) -> Weight;
@@ -69,11 +73,37 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add((140_000 as Weight).saturating_mul(b as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Placeholder until benchmarked, based on `set_invulnerables`.
+	fn add_invulnerable(b: u32, ) -> Weight {
+		(19_583_000 as Weight)
+			.saturating_add((140_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_invulnerables`.
+	fn remove_invulnerable(b: u32, ) -> Weight {
+		(19_583_000 as Weight)
+			.saturating_add((140_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn set_desired_candidates() -> Weight {
 		(16_810_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn set_candidacy_bond() -> Weight {
+		(17_450_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_candidacy_bond`.
+	fn set_max_candidacy_bond() -> Weight {
+		(17_450_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_candidacy_bond`.
+	fn set_kick_threshold() -> Weight {
 		(17_450_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
@@ -140,11 +170,37 @@ impl WeightInfo for () {
 			.saturating_add((140_000 as Weight).saturating_mul(b as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Placeholder until benchmarked, based on `set_invulnerables`.
+	fn add_invulnerable(b: u32, ) -> Weight {
+		(19_583_000 as Weight)
+			.saturating_add((140_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_invulnerables`.
+	fn remove_invulnerable(b: u32, ) -> Weight {
+		(19_583_000 as Weight)
+			.saturating_add((140_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	fn set_desired_candidates() -> Weight {
 		(16_810_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	fn set_candidacy_bond() -> Weight {
+		(17_450_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_candidacy_bond`.
+	fn set_max_candidacy_bond() -> Weight {
+		(17_450_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_candidacy_bond`.
+	fn set_kick_threshold() -> Weight {
 		(17_450_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
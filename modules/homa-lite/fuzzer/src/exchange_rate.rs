@@ -0,0 +1,50 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fuzzes the Staking to Liquid conversions used when claiming and restaking.
+//!
+//! Run with `cargo hfuzz run exchange_rate` from this directory.
+
+use honggfuzz::fuzz;
+use module_homa_lite::{liquid_to_staking, staking_to_liquid, TotalIssuanceInfo};
+
+fn main() {
+	loop {
+		fuzz!(|data: (u128, u128, u128)| {
+			let (staking_total, liquid_total, staked_amount) = data;
+			// A user's pending amount is always part of the batch's staking total.
+			if staked_amount > staking_total {
+				return;
+			}
+			let info = TotalIssuanceInfo {
+				staking_total,
+				liquid_total,
+			};
+			let rate = match info.exchange_rate() {
+				Some(rate) => rate,
+				None => return,
+			};
+			if let Some(liquid) = staking_to_liquid(staked_amount, rate) {
+				assert!(liquid <= liquid_total, "claimed more than the available liquid");
+				if let Some(restaked) = liquid_to_staking(liquid, rate) {
+					assert!(restaked <= staked_amount, "restaked more than was staked");
+				}
+			}
+		});
+	}
+}
@@ -0,0 +1,79 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for homa lite module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+pub use module_homa_lite::{BatchIndex, ProtocolStatus, Ratio, SettlementKind, TotalIssuanceInfo};
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait HomaLiteApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The total issuance info recorded for a batch, or `None` if the batch has not been
+		/// processed.
+		fn batch_issuance_info(batch: BatchIndex) -> Option<TotalIssuanceInfo>;
+
+		/// The user's unclaimed pending amounts, in ascending batch order.
+		fn pending_mints(who: AccountId) -> Vec<(BatchIndex, Balance)>;
+
+		/// At most `limit` of the user's unclaimed pending amounts, in ascending batch order,
+		/// starting from `start_batch`.
+		fn pending_mints_paged(who: AccountId, start_batch: BatchIndex, limit: u32) -> Vec<(BatchIndex, Balance)>;
+
+		/// The Liquid currency the user would receive for their pending amount in `batch` if it
+		/// were issued now with the given `staking_total`.
+		fn simulate_claim(who: AccountId, batch: BatchIndex, staking_total: Balance) -> Balance;
+
+		/// The total Staking currency in pending amounts that have not yet been claimed.
+		fn total_pending_staking() -> Balance;
+
+		/// Of the `limit` batches from `start_batch` onwards, the processed ones that still have
+		/// unclaimed pending amounts, in ascending batch order.
+		fn claimable_batches(start_batch: BatchIndex, limit: u32) -> Vec<BatchIndex>;
+
+		/// A summary of the module's current state.
+		fn protocol_status() -> ProtocolStatus;
+
+		/// The Staking to Liquid exchange rate of the most recently processed batch.
+		fn exchange_rate() -> Option<Ratio>;
+
+		/// How much the Liquid currency the user has claimed from `since_batch` onwards has grown
+		/// in value against the Staking currency.
+		fn realized_yield(who: AccountId, since_batch: BatchIndex) -> Option<Ratio>;
+
+		/// The user's most recent claims, as the batch, Liquid currency received and exchange
+		/// rate, oldest first.
+		fn claim_history(who: AccountId) -> Vec<(BatchIndex, Balance, Ratio)>;
+
+		/// How the user's pending amount in `batch` was settled, or `None` if it is still pending,
+		/// never existed, or its settlement has been pruned.
+		fn mint_settlement(who: AccountId, batch: BatchIndex) -> Option<SettlementKind>;
+
+		/// The Liquid currency the user can claim now for their pending amounts in processed
+		/// batches.
+		fn total_claimable(who: AccountId) -> Balance;
+	}
+}
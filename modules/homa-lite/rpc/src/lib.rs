@@ -0,0 +1,354 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the homa lite module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_homa_lite_rpc_runtime_api::{BatchIndex, ProtocolStatus, Ratio, SettlementKind, TotalIssuanceInfo};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as HomaLiteClient;
+pub use module_homa_lite_rpc_runtime_api::HomaLiteApi as HomaLiteRuntimeApi;
+
+#[rpc]
+pub trait HomaLiteApi<BlockHash, AccountId, Balance> {
+	#[rpc(name = "homaLite_getBatchInfo")]
+	fn get_batch_info(&self, batch: BatchIndex, at: Option<BlockHash>) -> Result<Option<TotalIssuanceInfo>>;
+
+	#[rpc(name = "homaLite_getPendingMints")]
+	fn get_pending_mints(&self, who: AccountId, at: Option<BlockHash>) -> Result<Vec<(BatchIndex, Balance)>>;
+
+	/// Returns at most `limit` pending mints, in ascending batch order, starting from
+	/// `start_batch`. To get the next page, pass the last returned batch plus 1.
+	#[rpc(name = "homaLite_getPendingMintsPaged")]
+	fn get_pending_mints_paged(
+		&self,
+		who: AccountId,
+		start_batch: BatchIndex,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(BatchIndex, Balance)>>;
+
+	#[rpc(name = "homaLite_simulateClaim")]
+	fn simulate_claim(
+		&self,
+		who: AccountId,
+		batch: BatchIndex,
+		staking_total: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Balance>;
+
+	#[rpc(name = "homaLite_getTotalPendingStaking")]
+	fn get_total_pending_staking(&self, at: Option<BlockHash>) -> Result<Balance>;
+
+	/// Returns the processed batches that still have unclaimed pending amounts, of the `limit`
+	/// batches from `start_batch` onwards.
+	#[rpc(name = "homaLite_getClaimableBatches")]
+	fn get_claimable_batches(
+		&self,
+		start_batch: BatchIndex,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<BatchIndex>>;
+
+	/// Returns the current batch, exchange rate, total pending, Liquid issuance and paused state
+	/// in a single call.
+	#[rpc(name = "homaLite_getProtocolStatus")]
+	fn get_protocol_status(&self, at: Option<BlockHash>) -> Result<ProtocolStatus>;
+
+	/// Returns the exchange rate of the most recently processed batch. Pass a historical block
+	/// hash to get the rate as of that block.
+	#[rpc(name = "homaLite_getExchangeRate")]
+	fn get_exchange_rate(&self, at: Option<BlockHash>) -> Result<Option<Ratio>>;
+
+	#[rpc(name = "homaLite_getRealizedYield")]
+	fn get_realized_yield(
+		&self,
+		who: AccountId,
+		since_batch: BatchIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<Ratio>>;
+
+	/// Returns the user's most recent claims, oldest first.
+	#[rpc(name = "homaLite_getClaimHistory")]
+	fn get_claim_history(&self, who: AccountId, at: Option<BlockHash>) -> Result<Vec<(BatchIndex, Balance, Ratio)>>;
+
+	/// Returns how the user's pending amount in a batch was settled, if it has been.
+	#[rpc(name = "homaLite_getMintSettlement")]
+	fn get_mint_settlement(
+		&self,
+		who: AccountId,
+		batch: BatchIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<SettlementKind>>;
+
+	/// Returns the Liquid currency the user can claim now across all processed batches.
+	#[rpc(name = "homaLite_getTotalClaimable")]
+	fn get_total_claimable(&self, who: AccountId, at: Option<BlockHash>) -> Result<Balance>;
+}
+
+/// A struct that implements the [`HomaLiteApi`].
+pub struct HomaLite<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> HomaLite<C, B> {
+	/// Create new `HomaLite` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		HomaLite {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance> HomaLiteApi<<Block as BlockT>::Hash, AccountId, Balance> for HomaLite<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: HomaLiteRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec,
+{
+	fn get_batch_info(
+		&self,
+		batch: BatchIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TotalIssuanceInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.batch_issuance_info(&at, batch).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get batch issuance info.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pending_mints(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(BatchIndex, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.pending_mints(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pending mints.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pending_mints_paged(
+		&self,
+		who: AccountId,
+		start_batch: BatchIndex,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(BatchIndex, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.pending_mints_paged(&at, who, start_batch, limit)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pending mints.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn simulate_claim(
+		&self,
+		who: AccountId,
+		batch: BatchIndex,
+		staking_total: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.simulate_claim(&at, who, batch, staking_total)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to simulate claim.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_total_pending_staking(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.total_pending_staking(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get total pending staking.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_claimable_batches(
+		&self,
+		start_batch: BatchIndex,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<BatchIndex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.claimable_batches(&at, start_batch, limit).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get claimable batches.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_protocol_status(&self, at: Option<<Block as BlockT>::Hash>) -> Result<ProtocolStatus> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.protocol_status(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get protocol status.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_exchange_rate(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Ratio>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.exchange_rate(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get exchange rate.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_realized_yield(
+		&self,
+		who: AccountId,
+		since_batch: BatchIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Ratio>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.realized_yield(&at, who, since_batch).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get realized yield.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_claim_history(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(BatchIndex, Balance, Ratio)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.claim_history(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get claim history.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_mint_settlement(
+		&self,
+		who: AccountId,
+		batch: BatchIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SettlementKind>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.mint_settlement(&at, who, batch).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get mint settlement.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_total_claimable(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.total_claimable(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get total claimable.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
@@ -39,7 +39,13 @@ benchmarks! {
 		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
 	}: _(RawOrigin::Signed(caller), amount)
 
-	issue {}: _(RawOrigin::Root, 1_000_000_000)
+	issue {
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
+		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller).into(), amount)?;
+	}: _(RawOrigin::Root, amount)
 
 	claim{
 		let amount = 1_000_000_000;
@@ -49,11 +55,147 @@ benchmarks! {
 		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
 		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount)?;
 		module::Pallet::<T>::issue(RawOrigin::Root.into(), amount)?;
-	}: _(RawOrigin::Signed(caller), caller.clone(), 0)
+	}: _(RawOrigin::Signed(caller), caller.clone(), BatchIndex(0))
+
+	claim_all{
+		let b in 1 .. T::MaxPendingBatchesPerUser::get();
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::LiquidCurrencyId::get(), &caller, amount)?;
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount * b as Balance)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
+		for _ in 0 .. b {
+			module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount)?;
+			module::Pallet::<T>::issue(RawOrigin::Root.into(), amount)?;
+		}
+	}: _(RawOrigin::Signed(caller))
 
 	set_stash_account_id{
+		let amount = 1_000_000_000;
 		let caller: T::AccountId = account("caller", 0, SEED);
+		let stash: T::AccountId = account("stash", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), stash)?;
+		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount)?;
 	}: _(RawOrigin::Root, caller)
+
+	reroll_pending{
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		module::PendingAmount::<T>::insert(BatchIndex(0), &caller, amount);
+		module::CurrentBatch::<T>::put(BatchIndex(1));
+	}: _(RawOrigin::Signed(caller), BatchIndex(0))
+
+	set_mint_bonus{
+		let bonus = Ratio::saturating_from_rational(1, 10);
+	}: _(RawOrigin::Root, bonus, 100u32.into())
+
+	set_permissionless_claim{
+	}: _(RawOrigin::Root, false)
+
+	force_refund_batch{
+		let n in 1 .. 100;
+		let amount = 1_000_000_000;
+		let stash: T::AccountId = account("stash", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &stash, amount * n as Balance)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), stash)?;
+		for i in 0 .. n {
+			let user: T::AccountId = account("user", i, SEED);
+			module::PendingAmount::<T>::insert(BatchIndex(0), &user, amount);
+		}
+	}: _(RawOrigin::Root, BatchIndex(0), n)
+
+	adjust_pending{
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let stash: T::AccountId = account("stash", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), stash)?;
+		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount / 2)?;
+	}: _(RawOrigin::Root, caller, BatchIndex(0), amount)
+
+	reconcile_stash{
+		let stash: T::AccountId = account("stash", 0, SEED);
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), stash)?;
+	}: _(RawOrigin::Root)
+
+	set_paused{
+	}: _(RawOrigin::Root, true)
+
+	split_current_batch{
+	}: _(RawOrigin::Root)
+
+	set_mint_router{
+		let router: T::AccountId = account("router", 0, SEED);
+	}: _(RawOrigin::Root, Some(router))
+
+	governance_burn_liquid{
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::LiquidCurrencyId::get(), &caller, amount)?;
+	}: _(RawOrigin::Root, caller, amount)
+
+	claim_and_restake{
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::LiquidCurrencyId::get(), &caller, amount)?;
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
+		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount)?;
+		module::Pallet::<T>::issue(RawOrigin::Root.into(), amount)?;
+	}: _(RawOrigin::Signed(caller), caller.clone(), BatchIndex(0))
+
+	set_liquid_currency_issuance_cap{
+	}: _(RawOrigin::Root, Some(1_000_000_000))
+
+	set_large_mint_threshold{
+	}: _(RawOrigin::Root, Some(1_000_000_000))
+
+	confirm_large_mint{
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
+		module::Pallet::<T>::set_large_mint_threshold(RawOrigin::Root.into(), Some(0))?;
+		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount)?;
+	}: _(RawOrigin::Root, caller, BatchIndex(0))
+
+	set_migration_enabled{
+	}: _(RawOrigin::Root, true)
+
+	add_to_blocklist{
+		let who: T::AccountId = account("who", 0, SEED);
+	}: _(RawOrigin::Root, who)
+
+	remove_from_blocklist{
+		let who: T::AccountId = account("who", 0, SEED);
+	}: _(RawOrigin::Root, who)
+
+	emergency_rebase{
+	}: _(RawOrigin::Root, Ratio::saturating_from_rational(9, 10))
+
+	set_instant_batch{
+	}: _(RawOrigin::Root, true)
+
+	set_auto_deposit{
+		let caller: T::AccountId = account("caller", 0, SEED);
+	}: _(RawOrigin::Signed(caller), true)
+
+	set_auto_compound{
+		let caller: T::AccountId = account("caller", 0, SEED);
+	}: _(RawOrigin::Signed(caller), true)
+
+	set_max_staking_total_change{
+	}: _(RawOrigin::Root, Some(Ratio::saturating_from_rational(1, 10)))
+
+	reject_large_mint{
+		let amount = 1_000_000_000;
+		let caller: T::AccountId = account("caller", 0, SEED);
+		<T as module::Config>::Currency::deposit(T::StakingCurrencyId::get(), &caller, amount)?;
+		module::Pallet::<T>::set_stash_account_id(RawOrigin::Root.into(), caller.clone())?;
+		module::Pallet::<T>::set_large_mint_threshold(RawOrigin::Root.into(), Some(0))?;
+		module::Pallet::<T>::request_mint(RawOrigin::Signed(caller.clone()).into(), amount)?;
+	}: _(RawOrigin::Root, caller, BatchIndex(0))
 }
 
 #[cfg(test)]
@@ -68,7 +210,11 @@ mod benchmark_mock {
 	use orml_traits::parameter_type_with_key;
 	use primitives::{Amount, TokenSymbol};
 	use sp_core::H256;
-	use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
+	use sp_runtime::{
+		testing::Header,
+		traits::{Bounded, IdentityLookup},
+		AccountId32,
+	};
 
 	mod homa_lite {
 		pub use super::super::*;
@@ -163,6 +309,25 @@ mod benchmark_mock {
 		pub const StakingCurrencyId: CurrencyId = KSM;
 		pub const LiquidCurrencyId: CurrencyId = LKSM;
 		pub const HomaLitePalletId: PalletId = PalletId(*b"aca/hmlt");
+		pub const MaxPendingBatchesPerUser: u32 = 3;
+		pub const MintCooldownBlocks: BlockNumber = 0;
+		pub const MaxMintRequestsPerCall: u32 = 50;
+		pub const ReconciliationTolerance: Balance = 1_000_000_000;
+		pub const MaxMemoLen: u32 = 32;
+		pub AutoPauseThreshold: Ratio = Ratio::max_value();
+		pub const IncentiveAccount: AccountId = AccountId32::new([12u8; 32]);
+		pub const MaxBatchSplits: u32 = 2;
+		pub const MaxAutoClaimsPerBlock: u32 = 3;
+		pub InitialExchangeRate: Ratio = Ratio::saturating_from_rational(10, 1);
+		pub const MaxClaimHistory: u32 = 3;
+		pub const MaxPendingPerUser: Balance = Balance::max_value();
+		pub const MaxPendingPerBatch: Balance = Balance::max_value();
+		pub const SettledMintsRetention: u32 = 10;
+		pub const MaxSettledMintsPruned: u32 = 100;
+		pub const BootstrapBatches: u32 = 0;
+		pub const MaxClaimsPerCall: u32 = 50;
+		pub AutoCompoundRatio: Ratio = Ratio::saturating_from_rational(1, 2);
+		pub const MaxParticipantsInEvent: u32 = 50;
 	}
 	ord_parameter_types! {
 		pub const Root: AccountId = ROOT;
@@ -177,6 +342,28 @@ mod benchmark_mock {
 		type PalletId = HomaLitePalletId;
 		type IssuerOrigin = EnsureRoot<AccountId>;
 		type GovernanceOrigin = EnsureRoot<AccountId>;
+		type MaxPendingBatchesPerUser = MaxPendingBatchesPerUser;
+		type MintCooldownBlocks = MintCooldownBlocks;
+		type MaxMintRequestsPerCall = MaxMintRequestsPerCall;
+		type BatchHandler = ();
+		type ReconciliationTolerance = ReconciliationTolerance;
+		type MaxMemoLen = MaxMemoLen;
+		type AutoPauseThreshold = AutoPauseThreshold;
+		type IncentiveAccount = IncentiveAccount;
+		type MaxBatchSplits = MaxBatchSplits;
+		type MaxAutoClaimsPerBlock = MaxAutoClaimsPerBlock;
+		type InitialExchangeRate = InitialExchangeRate;
+		type MaxClaimHistory = MaxClaimHistory;
+		type MaxPendingPerUser = MaxPendingPerUser;
+		type MaxPendingPerBatch = MaxPendingPerBatch;
+		type SettledMintsRetention = SettledMintsRetention;
+		type MaxSettledMintsPruned = MaxSettledMintsPruned;
+		type BootstrapRateProvider = ();
+		type BootstrapBatches = BootstrapBatches;
+		type MaxClaimsPerCall = MaxClaimsPerCall;
+		type ClaimDepositHandler = ();
+		type AutoCompoundRatio = AutoCompoundRatio;
+		type MaxParticipantsInEvent = MaxParticipantsInEvent;
 	}
 
 	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -242,9 +429,153 @@ mod tests {
 		});
 	}
 	#[test]
+	fn test_claim_all() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_claim_all::<Runtime>());
+		});
+	}
+	#[test]
 	fn test_set_stash_account_id() {
 		ExtBuilder::default().build().execute_with(|| {
 			assert_ok!(test_benchmark_set_stash_account_id::<Runtime>());
 		});
 	}
+	#[test]
+	fn test_reroll_pending() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_reroll_pending::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_mint_bonus() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_mint_bonus::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_permissionless_claim() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_permissionless_claim::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_force_refund_batch() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_force_refund_batch::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_adjust_pending() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_adjust_pending::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_reconcile_stash() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_reconcile_stash::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_paused() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_paused::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_split_current_batch() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_split_current_batch::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_mint_router() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_mint_router::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_governance_burn_liquid() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_governance_burn_liquid::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_claim_and_restake() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_claim_and_restake::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_liquid_currency_issuance_cap() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_liquid_currency_issuance_cap::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_large_mint_threshold() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_large_mint_threshold::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_confirm_large_mint() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_confirm_large_mint::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_migration_enabled() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_migration_enabled::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_add_to_blocklist() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_add_to_blocklist::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_remove_from_blocklist() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_remove_from_blocklist::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_emergency_rebase() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_emergency_rebase::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_instant_batch() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_instant_batch::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_auto_deposit() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_auto_deposit::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_auto_compound() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_auto_compound::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_set_max_staking_total_change() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_staking_total_change::<Runtime>());
+		});
+	}
+	#[test]
+	fn test_reject_large_mint() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_reject_large_mint::<Runtime>());
+		});
+	}
 }
@@ -37,25 +37,133 @@ mod mock;
 mod tests;
 pub mod weights;
 
-use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_support::{pallet_prelude::*, transactional, BoundedVec, PalletId};
 use frame_system::{ensure_signed, pallet_prelude::*};
-use module_support::Ratio;
+pub use module_support::Ratio;
+use module_support::{OnBatchProcessed, OnClaimDeposit, OnMigrate};
 use orml_traits::MultiCurrency;
-use primitives::{Balance, CurrencyId, EraIndex};
-use sp_runtime::{ArithmeticError, FixedPointNumber};
+use orml_utilities::{with_transaction_result, IterableStorageDoubleMapExtended};
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, Zero},
+	ArithmeticError, FixedPointNumber,
+};
 use sp_std::prelude::*;
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The index of a batch of mint requests. This is kept distinct from the relay chain's
+/// `EraIndex`, as batches are processed independently of relay chain eras.
+#[derive(Encode, Decode, Clone, Copy, Default, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BatchIndex(pub u32);
+
+impl BatchIndex {
+	/// Checked addition. Returns `None` if the batch index overflows.
+	pub fn checked_add(self, rhs: u32) -> Option<Self> {
+		self.0.checked_add(rhs).map(Self)
+	}
+
+	/// Saturating subtraction. Returns the first batch index on underflow.
+	pub fn saturating_sub(self, rhs: u32) -> Self {
+		Self(self.0.saturating_sub(rhs))
+	}
+}
+
+impl From<u32> for BatchIndex {
+	fn from(index: u32) -> Self {
+		Self(index)
+	}
+}
+
+impl From<BatchIndex> for u32 {
+	fn from(index: BatchIndex) -> Self {
+		index.0
+	}
+}
+
+/// Converts `staked_amount` of the Staking currency into the Liquid currency at `rate`, the Liquid
+/// currency per unit of Staking currency. Rounds down, so at a batch's exchange rate the result
+/// never exceeds its `liquid_total` as long as `staked_amount` does not exceed its
+/// `staking_total`. Returns `None` on overflow.
+pub fn staking_to_liquid(staked_amount: Balance, rate: Ratio) -> Option<Balance> {
+	rate.checked_mul_int(staked_amount)
+}
+
+/// Converts `liquid_amount` of the Liquid currency into the Staking currency at `rate`, the Liquid
+/// currency per unit of Staking currency. Rounds down, so converting the result of
+/// `staking_to_liquid` back at the same rate never returns more than the original amount. Returns
+/// `None` if `rate` is zero or on overflow.
+pub fn liquid_to_staking(liquid_amount: Balance, rate: Ratio) -> Option<Balance> {
+	if rate.is_zero() {
+		return None;
+	}
+	multiply_by_rational(liquid_amount, Ratio::accuracy(), rate.into_inner()).ok()
+}
+
 /// Used to record the total issuance of the currencies during a batch.
 /// This info is used to calculate exchange rate between Staking and Liquid currencies.
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct TotalIssuanceInfo {
 	pub staking_total: Balance,
 	pub liquid_total: Balance,
 }
 
+impl TotalIssuanceInfo {
+	/// The Liquid currency per unit of Staking currency, or `None` if `staking_total` is zero.
+	pub fn exchange_rate(&self) -> Option<Ratio> {
+		Ratio::checked_from_rational(self.liquid_total, self.staking_total)
+	}
+}
+
+/// A summary of the module's state, used by monitoring tools.
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ProtocolStatus {
+	/// The batch new mint requests go into.
+	pub current_batch: BatchIndex,
+	/// The Staking to Liquid exchange rate of the last processed batch, if any.
+	pub exchange_rate: Option<Ratio>,
+	/// The total Staking currency in pending amounts that have not yet been claimed.
+	pub total_pending_staking: Balance,
+	/// The total issuance of the Liquid currency.
+	pub liquid_total_issuance: Balance,
+	/// Whether minting, issuing and claiming are paused.
+	pub paused: bool,
+}
+
+/// How a user's pending amount in a batch was settled.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SettlementKind {
+	/// The Liquid currency was claimed.
+	Claimed,
+	/// The Staking currency was refunded by `force_refund_batch`.
+	Refunded,
+	/// The pending amount was cancelled by Governance via `adjust_pending`, and refunded.
+	Cancelled,
+	/// The pending amount was migrated out of this module.
+	Migrated,
+}
+
+/// The operation that changed the effective exchange rate.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RateChangeCause {
+	/// Batches were processed by `issue`, `force_issue`, or a mint while `InstantBatch` is on.
+	Issue,
+	/// Governance rebased the Liquid currency via `emergency_rebase`.
+	EmergencyRebase,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -87,6 +195,100 @@ pub mod module {
 
 		/// Origin represented by the Root or Governance
 		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of batches a single user can have pending amounts in.
+		#[pallet::constant]
+		type MaxPendingBatchesPerUser: Get<u32>;
+
+		/// The minimum number of blocks between two consecutive mint requests from the same user.
+		#[pallet::constant]
+		type MintCooldownBlocks: Get<Self::BlockNumber>;
+
+		/// The maximum number of mint requests allowed in a single `request_mint_batch` call, and
+		/// the maximum number of depositors a pool can mint for in a batch.
+		#[pallet::constant]
+		type MaxMintRequestsPerCall: Get<u32>;
+
+		/// Handler called when a batch is processed, with the batch and its Staking to Liquid
+		/// exchange rate. Its weight is not charged by `issue`, so it must be negligible.
+		type BatchHandler: OnBatchProcessed<BatchIndex, Ratio>;
+
+		/// The maximum shortfall of the relay chain stash account's balance, compared to the
+		/// pending amounts of the current batch, before a reconciliation mismatch is reported.
+		#[pallet::constant]
+		type ReconciliationTolerance: Get<Balance>;
+
+		/// The maximum length of the memo attached to a mint request.
+		#[pallet::constant]
+		type MaxMemoLen: Get<u32>;
+
+		/// The maximum deviation of a batch's exchange rate from the previous batch's rate, as a
+		/// ratio of the previous rate, before the module is automatically paused.
+		#[pallet::constant]
+		type AutoPauseThreshold: Get<Ratio>;
+
+		/// The account mint bonuses and other incentives are paid from. It is kept separate from
+		/// the treasury, and is topped up by Governance via a transfer.
+		type IncentiveAccount: Get<Self::AccountId>;
+
+		/// The maximum number of batches that can be split off the current batch before it is
+		/// processed.
+		#[pallet::constant]
+		type MaxBatchSplits: Get<u32>;
+
+		/// The maximum number of pending amounts automatically claimed in each block.
+		#[pallet::constant]
+		type MaxAutoClaimsPerBlock: Get<u32>;
+
+		/// The Staking to Liquid exchange rate used to issue a batch while there is no Liquid
+		/// currency in circulation, such as the first batch on a new chain.
+		#[pallet::constant]
+		type InitialExchangeRate: Get<Ratio>;
+
+		/// The maximum number of claims kept in each user's claim history.
+		#[pallet::constant]
+		type MaxClaimHistory: Get<u32>;
+
+		/// The maximum amount of Staking currency a single user can have pending in a batch.
+		#[pallet::constant]
+		type MaxPendingPerUser: Get<Balance>;
+
+		/// The maximum total amount of Staking currency that can be pending in a batch.
+		#[pallet::constant]
+		type MaxPendingPerBatch: Get<Balance>;
+
+		/// The number of batches settlements are kept for, after the batch they are in.
+		#[pallet::constant]
+		type SettledMintsRetention: Get<u32>;
+
+		/// The maximum number of expired settlements pruned each time batches are processed.
+		#[pallet::constant]
+		type MaxSettledMintsPruned: Get<u32>;
+
+		/// If it provides a rate, batches are issued at it instead of the rate computed from the
+		/// total issuances, until `BootstrapBatches` batches have been processed.
+		type BootstrapRateProvider: Get<Option<Ratio>>;
+
+		/// The number of batches that are issued at the `BootstrapRateProvider` rate.
+		#[pallet::constant]
+		type BootstrapBatches: Get<u32>;
+
+		/// The maximum number of claims that can be submitted in a single `claim_many` call.
+		#[pallet::constant]
+		type MaxClaimsPerCall: Get<u32>;
+
+		/// Handler that moves claimed Liquid currency into an external savings position, for
+		/// users who turned on `AutoDeposit`.
+		type ClaimDepositHandler: OnClaimDeposit<Self::AccountId, Balance>;
+
+		/// The fraction of the Liquid currency claimed for users with `AutoCompound` turned on
+		/// that is restaked into the current batch.
+		#[pallet::constant]
+		type AutoCompoundRatio: Get<Ratio>;
+
+		/// The maximum number of participants listed in a `BatchClosed` event.
+		#[pallet::constant]
+		type MaxParticipantsInEvent: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -98,45 +300,498 @@ pub mod module {
 		RelayChainStashAccountNotSet,
 		/// The total issuance for the Staking currency must be more than zero.
 		InvalidStakedCurrencyTotalIssuance,
+		/// The stash account cannot be set to this pallet's account or the default account.
+		InvalidStashAccount,
+		/// The user already has pending amounts in the maximum number of batches allowed.
+		TooManyPendingBatches,
+		/// The batch has already been processed.
+		BatchAlreadyProcessed,
+		/// The batch is neither the current batch nor one split off it.
+		BatchNotOpen,
+		/// Pending amounts cannot be rerolled into the batch they are already in.
+		CannotRerollIntoSameBatch,
+		/// The user has no pending amount in the given batch.
+		NoPendingAmount,
+		/// The user has requested a mint too recently and must wait for the cooldown to expire.
+		MintCooldownActive,
+		/// Too many mint requests were submitted in a single call.
+		TooManyMintRequests,
+		/// The claim would take the total Liquid currency claimed for the batch above the batch's
+		/// liquid total.
+		BatchLiquidExhausted,
+		/// Minting and claiming are paused.
+		ModulePaused,
+		/// Claims are owner-only, and the caller is neither the owner of the pending amount nor
+		/// Governance.
+		ClaimNotPermitted,
+		/// The incentive account does not have enough Liquid currency to pay the bonus.
+		InsufficientIncentiveFunds,
+		/// The current batch has already been split the maximum number of times.
+		TooManyBatchSplits,
+		/// The claim would take the total issuance of the Liquid currency above the cap.
+		ExceededLiquidIssuanceCap,
+		/// The batch was issued with a zero exchange rate, so claiming it would mint nothing.
+		ZeroExchangeRate,
+		/// The user has no unconfirmed large mint in the given batch.
+		NoUnconfirmedMint,
+		/// Migrating pending amounts has not been enabled by Governance.
+		MigrationNotEnabled,
+		/// The account is on the blocklist, and cannot mint or claim.
+		AccountBlocked,
+		/// The increase would take the user's pending amount in the batch above
+		/// `T::MaxPendingPerUser`.
+		ExceededUserPendingCap,
+		/// The increase would take the total pending amount of the batch above
+		/// `T::MaxPendingPerBatch`.
+		ExceededBatchPendingCap,
+		/// The rebase factor must be more than zero.
+		InvalidRebaseFactor,
+		/// Too many claims were submitted in a single call.
+		TooManyClaims,
+		/// Nothing is pending in the batches to be issued.
+		NoPendingMints,
+		/// The Staking currency total differs from the previous batch's by more than
+		/// `MaxStakingTotalChange` allows.
+		StakingTotalChangeTooLarge,
+		/// Pooled claims pay their depositors, so cannot be restaked.
+		CannotRestakePooledClaim,
+		/// Routed claims are minted into the mint router, so cannot be restaked.
+		CannotRestakeRoutedClaim,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
-	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::metadata(T::AccountId = "AccountId", T::BlockNumber = "BlockNumber")]
 	pub enum Event<T: Config> {
 		/// The user has requested some Staking currency to be used to mint Liquid Currency.
-		/// \[batch, user, amount\]
-		MintRequested(EraIndex, T::AccountId, Balance),
+		/// \[batch, user, amount, total_pending, memo\]
+		MintRequested(
+			BatchIndex,
+			T::AccountId,
+			Balance,
+			Balance,
+			BoundedVec<u8, T::MaxMemoLen>,
+		),
 
 		/// The current batch has been processed. Mint requests can now be completed. \[batch,
 		/// staking_total_issuance, liquid_total_issuance\]
-		BatchProcessed(EraIndex, Balance, Balance),
+		BatchProcessed(BatchIndex, Balance, Balance),
 
 		/// The user has claimed some Liquid Currency. \[batch, user, amount\]
-		LiquidCurrencyClaimed(EraIndex, T::AccountId, Balance),
+		LiquidCurrencyClaimed(BatchIndex, T::AccountId, Balance),
 
 		/// The relay chain's stash account ID has been updated.\[new_stash_account\]
 		RelayChainStashAccountUpdated(T::AccountId),
+
+		/// The user's pending amount has been moved into the current batch. \[from_batch,
+		/// to_batch, user, amount\]
+		PendingRerolled(BatchIndex, BatchIndex, T::AccountId, Balance),
+
+		/// Pending amounts in an unprocessed batch have been refunded by Governance. \[batch,
+		/// users_refunded, total_refunded\]
+		BatchForceRefunded(BatchIndex, u32, Balance),
+
+		/// A user's pending amount has been corrected by Governance. \[batch, user, old_amount,
+		/// new_amount\]
+		PendingAdjusted(BatchIndex, T::AccountId, Balance, Balance),
+
+		/// The relay chain stash account holds less Staking currency than the pending amounts of
+		/// the unprocessed batches. \[expected, actual\]
+		ReconciliationMismatch(Balance, Balance),
+
+		/// A batch's exchange rate deviated too far from the previous batch's, and the module has
+		/// been paused. \[old_rate, new_rate\]
+		AutoPaused(Ratio, Ratio),
+
+		/// The module has been paused or unpaused by Governance. \[paused\]
+		PausedSet(bool),
+
+		/// A mint bonus has been set for claims made until the given block. \[bonus, until_block\]
+		MintBonusSet(Ratio, T::BlockNumber),
+
+		/// Whether anyone can claim on behalf of a user has been updated. \[permissionless\]
+		PermissionlessClaimSet(bool),
+
+		/// The current batch has been split, and new mints go into a new batch. The old batch is
+		/// processed together with the new one. \[old_batch, new_batch\]
+		BatchSplit(BatchIndex, BatchIndex),
+
+		/// The mint router has been updated. \[router\]
+		MintRouterSet(Option<T::AccountId>),
+
+		/// Claimed Liquid currency has been deposited to the mint router instead of the user.
+		/// \[batch, user, router, amount\]
+		MintRouted(BatchIndex, T::AccountId, T::AccountId, Balance),
+
+		/// Governance has burned Liquid currency from an account. \[from, amount\]
+		LiquidBurnedByGovernance(T::AccountId, Balance),
+
+		/// The cap on the total issuance of the Liquid currency has been updated. \[cap\]
+		LiquidCurrencyIssuanceCapSet(Option<Balance>),
+
+		/// A pool has requested a mint on behalf of its depositors. \[batch, pool, depositors,
+		/// amount\]
+		PooledMintRequested(BatchIndex, T::AccountId, u32, Balance),
+
+		/// A pool depositor has received the Liquid currency minted for their share.
+		/// \[batch, pool, depositor, amount\]
+		PooledLiquidClaimed(BatchIndex, T::AccountId, T::AccountId, Balance),
+
+		/// A mint above the large mint threshold is awaiting confirmation by Governance.
+		/// \[batch, user, amount\]
+		LargeMintPending(BatchIndex, T::AccountId, Balance),
+
+		/// Governance has confirmed a large mint, and added it to the current batch.
+		/// \[requested_batch, current_batch, user, amount\]
+		LargeMintConfirmed(BatchIndex, BatchIndex, T::AccountId, Balance),
+
+		/// Governance has rejected a large mint, and refunded the Staking currency to the user.
+		/// \[batch, user, amount\]
+		LargeMintRejected(BatchIndex, T::AccountId, Balance),
+
+		/// The large mint threshold has been updated. \[threshold\]
+		LargeMintThresholdSet(Option<Balance>),
+
+		/// The maximum change in the Staking currency total per issue has been updated.
+		/// \[max_change\]
+		MaxStakingTotalChangeSet(Option<Ratio>),
+
+		/// A sponsor has paid for claiming a user's Liquid currency.
+		/// \[batch, sponsor, beneficiary, amount\]
+		ClaimSponsored(BatchIndex, T::AccountId, T::AccountId, Balance),
+
+		/// Whether pending amounts can be migrated has been updated. \[enabled\]
+		MigrationEnabledSet(bool),
+
+		/// A user's pending amount has been migrated out of this module. \[batch, user, amount\]
+		PendingMigrated(BatchIndex, T::AccountId, Balance),
+
+		/// An account has been added to the blocklist. \[who\]
+		BlocklistAdded(T::AccountId),
+
+		/// An account has been removed from the blocklist. \[who\]
+		BlocklistRemoved(T::AccountId),
+
+		/// Governance has rebased the Liquid currency, scaling the exchange rate of all future
+		/// claims. \[factor\]
+		EmergencyRebase(Ratio),
+
+		/// The effective exchange rate of claims has changed, because batches were processed or
+		/// Governance rebased the Liquid currency. Only emitted for those, as changes to pending
+		/// amounts, e.g. by `adjust_pending` or restakes, only affect unprocessed batches.
+		/// \[cause, old_rate, new_rate\]
+		RateChanged(RateChangeCause, Option<Ratio>, Option<Ratio>),
+
+		/// Instant batch processing has been turned on or off. \[enabled\]
+		InstantBatchSet(bool),
+
+		/// `BootstrapBatches` batches have been processed, and batches from now on are issued at
+		/// the rate computed from the total issuances. \[last_bootstrap_batch\]
+		BootstrapEnded(BatchIndex),
+
+		/// A keeper has claimed the Liquid currency of many users at once. \[keeper, claimed\]
+		ClaimedMany(T::AccountId, u32),
+
+		/// A user has turned depositing claimed Liquid currency into the savings position on or
+		/// off. \[who, enabled\]
+		AutoDepositSet(T::AccountId, bool),
+
+		/// Claimed Liquid currency has been deposited into the user's savings position.
+		/// \[batch, user, amount\]
+		ClaimAutoDeposited(BatchIndex, T::AccountId, Balance),
+
+		/// A user has turned auto-compounding of claims on or off. \[who, enabled\]
+		AutoCompoundSet(T::AccountId, bool),
+
+		/// Part of a claim has been restaked into the current batch. \[batch, user,
+		/// liquid_amount, staking_amount\]
+		AutoCompounded(BatchIndex, T::AccountId, Balance, Balance),
+
+		/// Part of a claim could not be restaked, e.g. because it would exceed a pending cap, so
+		/// it was left in the user's account instead. \[batch, user, liquid_amount\]
+		AutoCompoundFailed(BatchIndex, T::AccountId, Balance),
+
+		/// The Staking currency of the unprocessed batches has been moved to a new relay chain
+		/// stash account. \[old_stash, new_stash, amount\]
+		StashFundsMigrated(T::AccountId, T::AccountId, Balance),
+
+		/// A batch has been processed, with the users that have a pending amount in it. If there
+		/// are more than `T::MaxParticipantsInEvent` of them, the list is left empty and
+		/// `omitted` is set. \[batch, participants, omitted\]
+		BatchClosed(BatchIndex, BoundedVec<T::AccountId, T::MaxParticipantsInEvent>, bool),
 	}
 
 	/// Stores the amount of Staking currency the user has exchanged.
-	/// PendingAmount: double_map: (batch: EraIndex, user: T::AccountId) -> amount: Balance
+	/// PendingAmount: double_map: (batch: BatchIndex, user: T::AccountId) -> amount: Balance
 	#[pallet::storage]
 	#[pallet::getter(fn pending_amount)]
 	pub type PendingAmount<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, EraIndex, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+		StorageDoubleMap<_, Twox64Concat, BatchIndex, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// How each pending amount that has been removed was settled. Pruned
+	/// `T::SettledMintsRetention` batches after the batch is processed, and not recorded for
+	/// batches that have already been pruned.
+	/// SettledMints: double_map: (batch: BatchIndex, user: T::AccountId) -> kind: SettlementKind
+	#[pallet::storage]
+	#[pallet::getter(fn settled_mints)]
+	pub type SettledMints<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BatchIndex, Blake2_128Concat, T::AccountId, SettlementKind, OptionQuery>;
+
+	/// The oldest batch whose settlements may not have been pruned yet.
+	/// SettledMintsPruneCursor: value: batch: BatchIndex
+	#[pallet::storage]
+	#[pallet::getter(fn settled_mints_prune_cursor)]
+	pub type SettledMintsPruneCursor<T: Config> = StorageValue<_, BatchIndex, ValueQuery>;
+
+	/// The sum of all pending amounts in a batch.
+	/// BatchPendingTotal: map: batch: BatchIndex -> total: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn batch_pending_total)]
+	pub type BatchPendingTotal<T: Config> = StorageMap<_, Twox64Concat, BatchIndex, Balance, ValueQuery>;
+
+	/// The depositors' shares of a pool's pending amount. When the pool's pending amount is
+	/// claimed, each depositor receives the Liquid currency minted for their share.
+	/// PooledShares: double_map: ((batch: BatchIndex, pool: T::AccountId), depositor: T::AccountId) ->
+	/// share: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pooled_shares)]
+	pub type PooledShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(BatchIndex, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		Balance,
+		ValueQuery,
+	>;
+
+	/// The batches in which the user has pending amounts that have not yet been claimed.
+	/// PendingBatches: map: user: T::AccountId -> batches: BoundedVec<BatchIndex>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_batches)]
+	pub type PendingBatches<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<BatchIndex, T::MaxPendingBatchesPerUser>, ValueQuery>;
 
 	/// The total issuance info for each batch. Used to calculate Staking to Liquid exchange rate.
-	/// BatchTotalIssuanceInfo: map: batch: EraIndex -> batch_total: TotalIssuanceInfo
+	/// BatchTotalIssuanceInfo: map: batch: BatchIndex -> batch_total: TotalIssuanceInfo
 	#[pallet::storage]
 	#[pallet::getter(fn batch_total_issuance_info)]
-	pub type BatchTotalIssuanceInfo<T: Config> = StorageMap<_, Twox64Concat, EraIndex, TotalIssuanceInfo, OptionQuery>;
+	pub type BatchTotalIssuanceInfo<T: Config> =
+		StorageMap<_, Twox64Concat, BatchIndex, TotalIssuanceInfo, OptionQuery>;
+
+	/// The total amount of Liquid currency claimed for each batch. Claims can never exceed the
+	/// batch's recorded `liquid_total`.
+	/// ClaimedLiquid: map: batch: BatchIndex -> claimed: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn claimed_liquid)]
+	pub type ClaimedLiquid<T: Config> = StorageMap<_, Twox64Concat, BatchIndex, Balance, ValueQuery>;
 
 	/// The batch that is currency active
-	/// CurrentBatch: value: batch: EraIndex
+	/// CurrentBatch: value: batch: BatchIndex
 	#[pallet::storage]
 	#[pallet::getter(fn current_batch)]
-	pub type CurrentBatch<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+	pub type CurrentBatch<T: Config> = StorageValue<_, BatchIndex, ValueQuery>;
+
+	/// The batches split off the current batch that have not yet been processed. They are
+	/// processed together with the current batch.
+	/// SplitBatches: value: batches: BoundedVec<BatchIndex>
+	#[pallet::storage]
+	#[pallet::getter(fn split_batches)]
+	pub type SplitBatches<T: Config> = StorageValue<_, BoundedVec<BatchIndex, T::MaxBatchSplits>, ValueQuery>;
+
+	/// The block at which the user last requested a mint.
+	/// LastMintBlock: map: user: T::AccountId -> block: T::BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn last_mint_block)]
+	pub type LastMintBlock<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	/// The bonus paid on top of the Liquid currency claimed, and the last block it applies to.
+	/// The bonus is funded from `T::IncentiveAccount`.
+	/// MintBonus: value: (bonus: Ratio, until_block: T::BlockNumber)
+	#[pallet::storage]
+	#[pallet::getter(fn mint_bonus)]
+	pub type MintBonus<T: Config> = StorageValue<_, (Ratio, T::BlockNumber), OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultPermissionlessClaim() -> bool {
+		true
+	}
+
+	/// Whether anyone can claim the Liquid currency on behalf of a user. If false, only the user
+	/// or Governance can claim.
+	/// PermissionlessClaim: value: permissionless: bool
+	#[pallet::storage]
+	#[pallet::getter(fn permissionless_claim)]
+	pub type PermissionlessClaim<T: Config> = StorageValue<_, bool, ValueQuery, DefaultPermissionlessClaim>;
+
+	#[pallet::type_value]
+	pub fn DefaultRebaseFactor() -> Ratio {
+		Ratio::one()
+	}
+
+	/// The factor the exchange rate of every claim is multiplied by. Only changed by Governance
+	/// in an emergency, such as a major slash.
+	/// RebaseFactor: value: factor: Ratio
+	#[pallet::storage]
+	#[pallet::getter(fn rebase_factor)]
+	pub type RebaseFactor<T: Config> = StorageValue<_, Ratio, ValueQuery, DefaultRebaseFactor>;
+
+	/// The total Staking currency in pending amounts that have not yet been claimed.
+	/// TotalPendingStaking: value: total: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_pending_staking)]
+	pub type TotalPendingStaking<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// Whether minting, issuing and claiming are paused.
+	/// Paused: value: paused: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_paused)]
+	pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The user's most recent claims, oldest first.
+	/// ClaimHistory: map: who: T::AccountId -> history: BoundedVec<(batch: BatchIndex, amount: Balance,
+	/// rate: Ratio)>
+	#[pallet::storage]
+	#[pallet::getter(fn claim_history)]
+	pub type ClaimHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(BatchIndex, Balance, Ratio), T::MaxClaimHistory>,
+		ValueQuery,
+	>;
+
+	/// Accounts that are not allowed to mint or claim.
+	/// Blocklist: map: who: T::AccountId -> blocked: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_blocked)]
+	pub type Blocklist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Whether pending amounts can be migrated out of this module via `OnMigrate`.
+	/// MigrationEnabled: value: enabled: bool
+	#[pallet::storage]
+	#[pallet::getter(fn migration_enabled)]
+	pub type MigrationEnabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Whether `request_mint` immediately processes the batch it adds to, at the most recent
+	/// exchange rate. Only meant for development chains.
+	/// InstantBatch: value: enabled: bool
+	#[pallet::storage]
+	#[pallet::getter(fn instant_batch)]
+	pub type InstantBatch<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The number of batches that have been processed.
+	/// ProcessedBatchCount: value: count: u32
+	#[pallet::storage]
+	#[pallet::getter(fn processed_batch_count)]
+	pub type ProcessedBatchCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Whether the Liquid currency claimed for a user is deposited into their savings position via
+	/// `T::ClaimDepositHandler`.
+	/// AutoDeposit: map: AccountId => enabled: bool
+	#[pallet::storage]
+	#[pallet::getter(fn auto_deposit)]
+	pub type AutoDeposit<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Whether part of the Liquid currency claimed for a user is restaked into the current batch.
+	/// AutoCompound: map: AccountId => enabled: bool
+	#[pallet::storage]
+	#[pallet::getter(fn auto_compound)]
+	pub type AutoCompound<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// If set, claimed Liquid currency is deposited into this account, which is responsible for
+	/// forwarding it to the users, instead of directly into the users' accounts.
+	/// MintRouter: value: router: T::AccountId
+	#[pallet::storage]
+	#[pallet::getter(fn mint_router)]
+	pub type MintRouter<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The maximum total issuance of the Liquid currency that claims can mint up to. Unlimited if
+	/// not set.
+	/// LiquidCurrencyIssuanceCap: value: cap: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn liquid_currency_issuance_cap)]
+	pub type LiquidCurrencyIssuanceCap<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// Mints above this amount must be confirmed by Governance before they are added to a batch.
+	/// Disabled if not set.
+	/// LargeMintThreshold: value: threshold: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn large_mint_threshold)]
+	pub type LargeMintThreshold<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The maximum change in the Staking currency total from one issue to the next, as a ratio of
+	/// the previous batch's total. Unlimited if not set.
+	/// MaxStakingTotalChange: value: max_change: Ratio
+	#[pallet::storage]
+	#[pallet::getter(fn max_staking_total_change)]
+	pub type MaxStakingTotalChange<T: Config> = StorageValue<_, Ratio, OptionQuery>;
+
+	/// Large mints awaiting confirmation by Governance, by the batch they were requested in. The
+	/// Staking currency has already been transferred to the relay chain stash account.
+	/// UnconfirmedMints: double_map: (batch: BatchIndex, user: T::AccountId) -> amount: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn unconfirmed_mints)]
+	pub type UnconfirmedMints<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BatchIndex, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The sum of all large mints awaiting confirmation by Governance, in any batch.
+	/// TotalUnconfirmedMints: value: total: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_unconfirmed_mints)]
+	pub type TotalUnconfirmedMints<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The depositors' shares of a pool's large mint awaiting confirmation by Governance. They
+	/// are moved into `PooledShares` when the mint is confirmed.
+	/// UnconfirmedPooledShares: double_map: ((batch: BatchIndex, pool: T::AccountId), depositor:
+	/// T::AccountId) -> share: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn unconfirmed_pooled_shares)]
+	pub type UnconfirmedPooledShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(BatchIndex, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		Balance,
+		ValueQuery,
+	>;
+
+	/// The part of each pending amount that was restaked from claimed Liquid currency. Its
+	/// Staking currency is already staked on the relay chain, so it was never transferred to the
+	/// relay chain stash account.
+	/// RestakedPending: double_map: (batch: BatchIndex, user: T::AccountId) -> amount: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn restaked_pending)]
+	pub type RestakedPending<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BatchIndex, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The sum of the restaked parts of all pending amounts in a batch.
+	/// BatchRestakedTotal: map: batch: BatchIndex -> total: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn batch_restaked_total)]
+	pub type BatchRestakedTotal<T: Config> = StorageMap<_, Twox64Concat, BatchIndex, Balance, ValueQuery>;
+
+	/// The part of each large mint awaiting confirmation that was restaked from claimed Liquid
+	/// currency, by the batch it was requested in.
+	/// UnconfirmedRestaked: double_map: (batch: BatchIndex, user: T::AccountId) -> amount: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn unconfirmed_restaked)]
+	pub type UnconfirmedRestaked<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BatchIndex, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The sum of the restaked parts of all large mints awaiting confirmation, in any batch.
+	/// TotalUnconfirmedRestaked: value: total: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_unconfirmed_restaked)]
+	pub type TotalUnconfirmedRestaked<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The processed batch whose pending amounts are being automatically claimed, and the raw
+	/// storage key of the last pending amount visited, to resume from in the next block.
+	/// AutoClaimCursor: value: (batch: BatchIndex, last_key: Option<Vec<u8>>)
+	#[pallet::storage]
+	#[pallet::getter(fn auto_claim_cursor)]
+	pub type AutoClaimCursor<T: Config> = StorageValue<_, (BatchIndex, Option<Vec<u8>>), OptionQuery>;
 
 	/// The account in which the staking currency goes into to be transferred to the Relay chain.
 	/// RelayChainStashAccount: value: stash_account: AccountId
@@ -147,6 +802,13 @@ pub mod module {
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			Self::process_auto_claims(T::MaxAutoClaimsPerBlock::get())
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Request to mint some Liquid currency, by locking up the given amount of Staking
@@ -158,49 +820,76 @@ pub mod module {
 		#[pallet::weight(< T as Config >::WeightInfo::request_mint())]
 		#[transactional]
 		pub fn request_mint(origin: OriginFor<T>, amount: Balance) -> DispatchResult {
-			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+			Self::do_request_own_mint(origin, amount, Default::default())
+		}
 
-			let who = ensure_signed(origin)?;
-			let current_batch = Self::current_batch();
-			let staking_currency_id = T::StakingCurrencyId::get();
+		/// Request to mint some Liquid currency, the same way as `request_mint`, tagged with a memo
+		/// that is included in the `MintRequested` event but not stored.
+		///
+		/// Parameters:
+		/// - `amount`: The amount of Staking currency to be exchanged.
+		/// - `memo`: A reference of at most `T::MaxMemoLen` bytes, e.g. for exchanges to
+		///   reconcile mints with their internal ledger.
+		#[pallet::weight(< T as Config >::WeightInfo::request_mint())]
+		#[transactional]
+		pub fn request_mint_with_memo(
+			origin: OriginFor<T>,
+			amount: Balance,
+			memo: BoundedVec<u8, T::MaxMemoLen>,
+		) -> DispatchResult {
+			Self::do_request_own_mint(origin, amount, memo)
+		}
 
-			// TODO: Cross-chain transfer to the relay chain via XCM
-			T::Currency::transfer(staking_currency_id, &who, &stash_account, amount)?;
+		/// Request to mint some Liquid currency, and claim it straight away if the batch it is
+		/// added to has already been processed. Otherwise the amount is left pending as for
+		/// `request_mint`.
+		///
+		/// Parameters:
+		/// - `amount`: The amount of Staking currency to be exchanged.
+		#[pallet::weight(< T as Config >::WeightInfo::request_mint().saturating_add(< T as Config >::WeightInfo::claim()))]
+		#[transactional]
+		pub fn request_mint_and_claim(origin: OriginFor<T>, amount: Balance) -> DispatchResult {
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
 
-			PendingAmount::<T>::mutate(current_batch, &who, |current| {
-				*current = current.checked_add(amount).expect("Amount should not cause overflow.")
-			});
+			let who = ensure_signed(origin)?;
+			Self::update_last_mint_block(&who)?;
+			let batch = Self::current_batch();
+			Self::do_request_mint(&who, &stash_account, who.clone(), amount, Default::default())?;
+			if Self::instant_batch() {
+				Self::process_instant_batch()?;
+			}
 
-			Self::deposit_event(Event::<T>::MintRequested(current_batch, who, amount));
+			if Self::batch_total_issuance_info(batch).is_some() && Self::pending_amount(batch, &who) != 0 {
+				Self::do_claim(who, batch)?;
+			}
 			Ok(())
 		}
 
-		/// Process a batch.
-		/// It is then that we can issue Liquid currencies.
+		/// Process the current batch, and any batches split off it.
+		/// It is then that we can issue Liquid currencies. Fails if nothing is pending in them, see
+		/// `force_issue`.
 		/// Requires `T::IssuerOrigin`
 		///
 		/// Parameters:
 		/// - `staking_total`:
-		#[pallet::weight(< T as Config >::WeightInfo::issue())]
+		#[pallet::weight(< T as Config >::WeightInfo::issue().saturating_add(Pallet::<T>::close_batches_weight()))]
 		#[transactional]
 		pub fn issue(origin: OriginFor<T>, staking_total: Balance) -> DispatchResult {
 			T::IssuerOrigin::ensure_origin(origin)?;
-			ensure!(staking_total != 0, Error::<T>::InvalidStakedCurrencyTotalIssuance);
-
-			let current_batch = Self::current_batch();
-
-			let liquid_total = T::Currency::total_issuance(T::LiquidCurrencyId::get());
-			let total_for_batch = TotalIssuanceInfo {
-				staking_total,
-				liquid_total,
-			};
-
-			BatchTotalIssuanceInfo::<T>::insert(&current_batch, total_for_batch);
-			CurrentBatch::<T>::put(current_batch.checked_add(1).expect("Batch Index should not overflow."));
-
-			Self::deposit_event(Event::<T>::BatchProcessed(current_batch, staking_total, liquid_total));
+			Self::do_issue(staking_total, false)
+		}
 
-			Ok(())
+		/// Process the current batch, and any batches split off it, even if nothing is pending in
+		/// them.
+		/// Requires `T::IssuerOrigin`
+		///
+		/// Parameters:
+		/// - `staking_total`: The total amount of Staking currency staked.
+		#[pallet::weight(< T as Config >::WeightInfo::issue().saturating_add(Pallet::<T>::close_batches_weight()))]
+		#[transactional]
+		pub fn force_issue(origin: OriginFor<T>, staking_total: Balance) -> DispatchResult {
+			T::IssuerOrigin::ensure_origin(origin)?;
+			Self::do_issue(staking_total, true)
 		}
 
 		/// A function that allows the user to claim the Liquid currencies minted.
@@ -212,44 +901,1576 @@ pub mod module {
 		/// - `batch`: The batch index the user Staked their tokens.
 		#[pallet::weight(< T as Config >::WeightInfo::claim())]
 		#[transactional]
-		pub fn claim(origin: OriginFor<T>, who: T::AccountId, batch: EraIndex) -> DispatchResult {
-			ensure_signed(origin)?;
-			let staked_amount = Self::pending_amount(&batch, &who);
-			let total_info =
-				Self::batch_total_issuance_info(batch).ok_or(Error::<T>::LiquidCurrencyNotIssuedForThisBatch)?;
+		pub fn claim(origin: OriginFor<T>, who: T::AccountId, batch: BatchIndex) -> DispatchResult {
+			// Governance can always claim on behalf of a user.
+			if T::GovernanceOrigin::try_origin(origin.clone()).is_err() {
+				let caller = ensure_signed(origin)?;
+				ensure!(
+					Self::permissionless_claim() || caller == who,
+					Error::<T>::ClaimNotPermitted
+				);
+			}
+			Self::do_claim(who, batch)?;
+			Ok(())
+		}
 
-			// liquid_to_mint = staked_amount * liquid_total / staked_total
-			let exchange_ratio = Ratio::checked_from_rational(total_info.liquid_total, total_info.staking_total)
-				.ok_or(ArithmeticError::Overflow)?;
+		/// Claims the Liquid currency minted for every processed batch the caller has a pending
+		/// amount in. Pending amounts in unprocessed batches are left as they are.
+		#[pallet::weight(< T as Config >::WeightInfo::claim_all(T::MaxPendingBatchesPerUser::get()))]
+		#[transactional]
+		pub fn claim_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
-			let liquid_to_mint = exchange_ratio
-				.checked_mul_int(staked_amount)
-				.ok_or(ArithmeticError::Overflow)?;
+			// `PendingBatches` is bounded by `T::MaxPendingBatchesPerUser`, which the weight
+			// is benchmarked up to.
+			let batches: Vec<BatchIndex> = Self::pending_batches(&who)
+				.into_iter()
+				.filter(|batch| Self::batch_total_issuance_info(batch).is_some())
+				.collect();
+			for batch in batches.iter() {
+				Self::do_claim(who.clone(), *batch)?;
+			}
+			Ok(Some(T::WeightInfo::claim_all(batches.len() as u32)).into())
+		}
 
-			// Mint the liquid currency into the user's account.
-			T::Currency::deposit(T::LiquidCurrencyId::get(), &who, liquid_to_mint)?;
-			// Remove the pending request from storage
-			PendingAmount::<T>::remove(&batch, &who);
+		/// Claims the Liquid currency minted for a user, with the caller paying the transaction
+		/// fee. Lets third parties unblock claims for users who hold no native token. Subject to
+		/// the same permissions as `claim`.
+		///
+		/// Parameters:
+		/// - `who`: The user the claimed Liquid currency is for.
+		/// - `batch`: The batch index the user Staked their tokens.
+		#[pallet::weight(< T as Config >::WeightInfo::claim())]
+		#[transactional]
+		pub fn sponsor_claim(origin: OriginFor<T>, who: T::AccountId, batch: BatchIndex) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(
+				Self::permissionless_claim() || sponsor == who,
+				Error::<T>::ClaimNotPermitted
+			);
+			ensure!(Self::pending_amount(&batch, &who) != 0, Error::<T>::NoPendingAmount);
+
+			let amount = Self::do_claim(who.clone(), batch)?;
+			Self::deposit_event(Event::<T>::ClaimSponsored(batch, sponsor, who, amount));
+			Ok(())
+		}
+
+		/// Claims the Liquid currency of up to `max` of the given users and batches. Pairs that
+		/// cannot be claimed, e.g. because the batch is not processed yet, the amount has already
+		/// been claimed, or the caller is not permitted to claim for the user, are skipped.
+		///
+		/// Parameters:
+		/// - `claims`: The users and batches to claim for.
+		/// - `max`: The maximum number of claims made by this call.
+		#[pallet::weight(< T as Config >::WeightInfo::claim().saturating_mul(claims.len().min(*max as usize) as Weight))]
+		#[transactional]
+		pub fn claim_many(
+			origin: OriginFor<T>,
+			claims: Vec<(T::AccountId, BatchIndex)>,
+			max: u32,
+		) -> DispatchResultWithPostInfo {
+			let keeper = ensure_signed(origin)?;
+			ensure!(
+				claims.len() as u32 <= T::MaxClaimsPerCall::get(),
+				Error::<T>::TooManyClaims
+			);
+
+			let permissionless = Self::permissionless_claim();
+			let mut claimed: u32 = 0;
+			let mut skipped: u32 = 0;
+			for (who, batch) in claims {
+				if claimed >= max {
+					break;
+				}
+				let claimable = (permissionless || keeper == who)
+					&& Self::pending_amount(&batch, &who) != 0
+					&& Self::batch_total_issuance_info(&batch).is_some();
+				if claimable && with_transaction_result(|| Self::do_claim(who, batch).map(|_| ())).is_ok() {
+					claimed = claimed.saturating_add(1);
+				} else {
+					skipped = skipped.saturating_add(1);
+				}
+			}
 
-			Self::deposit_event(Event::<T>::LiquidCurrencyClaimed(batch, who, liquid_to_mint));
+			Self::deposit_event(Event::<T>::ClaimedMany(keeper, claimed));
+			Ok(Some(
+				T::WeightInfo::claim()
+					.saturating_mul(claimed as Weight)
+					.saturating_add(T::DbWeight::get().reads(2).saturating_mul(skipped as Weight)),
+			)
+			.into())
+		}
+
+		/// Sets whether the Liquid currency claimed for the caller is deposited into their savings
+		/// position, instead of being left in their free balance.
+		///
+		/// Parameters:
+		/// - `enabled`: Whether claims are deposited into the savings position.
+		#[pallet::weight(< T as Config >::WeightInfo::set_auto_deposit())]
+		#[transactional]
+		pub fn set_auto_deposit(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
+			if enabled {
+				AutoDeposit::<T>::insert(&who, true);
+			} else {
+				AutoDeposit::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::<T>::AutoDepositSet(who, enabled));
 			Ok(())
 		}
 
-		/// Updates the relay chain Stash Account ID.
-		/// Requires `T::GovernanceOrigin`
+		/// Sets whether `T::AutoCompoundRatio` of the Liquid currency claimed for the caller is
+		/// restaked into the current batch as a new pending amount.
 		///
 		/// Parameters:
-		/// - `new_account_id`: The new relay chain stash account.
-		#[pallet::weight(< T as Config >::WeightInfo::set_stash_account_id())]
+		/// - `enabled`: Whether claims are auto-compounded.
+		#[pallet::weight(< T as Config >::WeightInfo::set_auto_compound())]
 		#[transactional]
-		pub fn set_stash_account_id(origin: OriginFor<T>, new_account_id: T::AccountId) -> DispatchResult {
-			// This can only be called by Governance or ROOT.
-			T::GovernanceOrigin::ensure_origin(origin)?;
+		pub fn set_auto_compound(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			RelayChainStashAccount::<T>::put(new_account_id.clone());
-			Self::deposit_event(Event::<T>::RelayChainStashAccountUpdated(new_account_id));
+			if enabled {
+				AutoCompound::<T>::insert(&who, true);
+			} else {
+				AutoCompound::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::<T>::AutoCompoundSet(who, enabled));
 			Ok(())
 		}
+
+		/// Claims the Liquid currency minted for the user's pending amount in a processed batch,
+		/// and immediately restakes it into the current batch as a new pending amount. Any mint
+		/// bonus is paid to the user as usual and is not restaked. Pooled claims, and claims while
+		/// a mint router is set, cannot be restaked.
+		///
+		/// Parameters:
+		/// - `who`: The user whose pending amount is claimed and restaked.
+		/// - `batch`: The processed batch the user Staked their tokens in.
+		#[pallet::weight(< T as Config >::WeightInfo::claim_and_restake()
+			.saturating_add(T::DbWeight::get().reads_writes(3, 3)))]
+		#[transactional]
+		pub fn claim_and_restake(origin: OriginFor<T>, who: T::AccountId, batch: BatchIndex) -> DispatchResult {
+			// Governance can always claim on behalf of a user.
+			if T::GovernanceOrigin::try_origin(origin.clone()).is_err() {
+				let caller = ensure_signed(origin)?;
+				ensure!(
+					Self::permissionless_claim() || caller == who,
+					Error::<T>::ClaimNotPermitted
+				);
+			}
+
+			let staked_amount = Self::pending_amount(&batch, &who);
+			ensure!(staked_amount != 0, Error::<T>::NoPendingAmount);
+			ensure!(
+				PooledShares::<T>::iter_prefix((batch, who.clone())).next().is_none(),
+				Error::<T>::CannotRestakePooledClaim
+			);
+			ensure!(Self::mint_router().is_none(), Error::<T>::CannotRestakeRoutedClaim);
+			let total_info =
+				Self::effective_issuance_info(batch).ok_or(Error::<T>::LiquidCurrencyNotIssuedForThisBatch)?;
+			Self::claim_liquid(who.clone(), batch, false)?;
+
+			// Burn the Liquid currency minted into the user's account for the pending amount, and
+			// restake its Staking currency equivalent. The Staking currency is already on the relay
+			// chain, so nothing is transferred. Rounding down in both conversions means at most
+			// `staked_amount` is restaked.
+			let rate = total_info.exchange_rate().ok_or(ArithmeticError::Overflow)?;
+			let liquid_minted = staking_to_liquid(staked_amount, rate).ok_or(ArithmeticError::Overflow)?;
+			T::Currency::withdraw(T::LiquidCurrencyId::get(), &who, liquid_minted)?;
+			let restake_amount = liquid_to_staking(liquid_minted, rate).ok_or(ArithmeticError::Overflow)?;
+
+			Self::restake(who, restake_amount)
+		}
+
+		/// Updates the relay chain Stash Account ID. The Staking currency of the unprocessed
+		/// batches is moved from the old stash account to the new one, so it can still be refunded.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `new_account_id`: The new relay chain stash account.
+		#[pallet::weight(< T as Config >::WeightInfo::set_stash_account_id())]
+		#[transactional]
+		pub fn set_stash_account_id(origin: OriginFor<T>, new_account_id: T::AccountId) -> DispatchResult {
+			// This can only be called by Governance or ROOT.
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				new_account_id != Self::account_id() && new_account_id != T::AccountId::default(),
+				Error::<T>::InvalidStashAccount
+			);
+
+			if let Some(old_account_id) = Self::relay_chain_stash_account() {
+				let in_transit = Self::staking_in_transit();
+				if old_account_id != new_account_id && !in_transit.is_zero() {
+					// TODO: Cross-chain transfer on the relay chain via XCM
+					T::Currency::transfer(
+						T::StakingCurrencyId::get(),
+						&old_account_id,
+						&new_account_id,
+						in_transit,
+					)?;
+					Self::deposit_event(Event::<T>::StashFundsMigrated(
+						old_account_id,
+						new_account_id.clone(),
+						in_transit,
+					));
+				}
+			}
+
+			RelayChainStashAccount::<T>::put(new_account_id.clone());
+			Self::deposit_event(Event::<T>::RelayChainStashAccountUpdated(new_account_id));
+			Ok(())
+		}
+
+		/// Sets a bonus paid on top of every claim made until `until_block`. The bonus is a ratio
+		/// of the Liquid currency claimed, and is paid from `T::IncentiveAccount`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `bonus`: The ratio of the claimed amount paid as a bonus.
+		/// - `until_block`: The last block at which the bonus applies.
+		#[pallet::weight(< T as Config >::WeightInfo::set_mint_bonus())]
+		#[transactional]
+		pub fn set_mint_bonus(origin: OriginFor<T>, bonus: Ratio, until_block: T::BlockNumber) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			MintBonus::<T>::put((bonus, until_block));
+			Self::deposit_event(Event::<T>::MintBonusSet(bonus, until_block));
+			Ok(())
+		}
+
+		/// Pauses or unpauses minting, issuing and claiming.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `paused`: Whether the module is paused.
+		#[pallet::weight(< T as Config >::WeightInfo::set_paused())]
+		#[transactional]
+		pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			Paused::<T>::put(paused);
+			Self::deposit_event(Event::<T>::PausedSet(paused));
+			Ok(())
+		}
+
+		/// Sets whether anyone can claim the Liquid currency on behalf of a user. If not, claims
+		/// must be made by the user, or by Governance.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `permissionless`: Whether claims are permissionless.
+		#[pallet::weight(< T as Config >::WeightInfo::set_permissionless_claim())]
+		#[transactional]
+		pub fn set_permissionless_claim(origin: OriginFor<T>, permissionless: bool) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			PermissionlessClaim::<T>::put(permissionless);
+			Self::deposit_event(Event::<T>::PermissionlessClaimSet(permissionless));
+			Ok(())
+		}
+
+		/// Sets whether pending amounts can be migrated out of this module. Only enabled during a
+		/// coordinated migration to another module.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `enabled`: Whether pending amounts can be migrated.
+		#[pallet::weight(< T as Config >::WeightInfo::set_migration_enabled())]
+		#[transactional]
+		pub fn set_migration_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			MigrationEnabled::<T>::put(enabled);
+			Self::deposit_event(Event::<T>::MigrationEnabledSet(enabled));
+			Ok(())
+		}
+
+		/// Sets whether `request_mint` immediately processes the batch it adds to. Only meant for
+		/// development chains, where waiting for batches to be issued slows down testing.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `enabled`: Whether batches are processed instantly.
+		#[pallet::weight(< T as Config >::WeightInfo::set_instant_batch())]
+		#[transactional]
+		pub fn set_instant_batch(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			InstantBatch::<T>::put(enabled);
+			Self::deposit_event(Event::<T>::InstantBatchSet(enabled));
+			Ok(())
+		}
+
+		/// Blocks an account from minting or claiming.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `who`: The account to block.
+		#[pallet::weight(< T as Config >::WeightInfo::add_to_blocklist())]
+		#[transactional]
+		pub fn add_to_blocklist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			Blocklist::<T>::insert(&who, true);
+			Self::deposit_event(Event::<T>::BlocklistAdded(who));
+			Ok(())
+		}
+
+		/// Allows a blocked account to mint and claim again.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `who`: The account to unblock.
+		#[pallet::weight(< T as Config >::WeightInfo::remove_from_blocklist())]
+		#[transactional]
+		pub fn remove_from_blocklist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			Blocklist::<T>::remove(&who);
+			Self::deposit_event(Event::<T>::BlocklistRemoved(who));
+			Ok(())
+		}
+
+		/// Rebases the Liquid currency in an emergency, such as a major slash, by scaling the
+		/// exchange rate of all future claims by `factor`. Balances are not changed. The factor
+		/// replaces any previous one, and is not compounded with it.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `factor`: The factor future exchange rates are multiplied by.
+		#[pallet::weight(< T as Config >::WeightInfo::emergency_rebase())]
+		#[transactional]
+		pub fn emergency_rebase(origin: OriginFor<T>, factor: Ratio) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(!factor.is_zero(), Error::<T>::InvalidRebaseFactor);
+
+			let old_rate = Self::effective_exchange_rate();
+			RebaseFactor::<T>::put(factor);
+			Self::deposit_event(Event::<T>::RateChanged(
+				RateChangeCause::EmergencyRebase,
+				old_rate,
+				Self::effective_exchange_rate(),
+			));
+			Self::deposit_event(Event::<T>::EmergencyRebase(factor));
+			Ok(())
+		}
+
+		/// Sets the account claimed Liquid currency is deposited into, or claims deposit directly
+		/// into the users' accounts if `None`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `router`: The account responsible for forwarding claimed Liquid currency.
+		#[pallet::weight(< T as Config >::WeightInfo::set_mint_router())]
+		#[transactional]
+		pub fn set_mint_router(origin: OriginFor<T>, router: Option<T::AccountId>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			MintRouter::<T>::set(router.clone());
+			Self::deposit_event(Event::<T>::MintRouterSet(router));
+			Ok(())
+		}
+
+		/// Burns Liquid currency from an account, e.g. to remove Liquid currency minted in error.
+		/// This is an emergency tool for restoring the exchange rate.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `from`: The account to burn the Liquid currency from.
+		/// - `amount`: The amount of Liquid currency to burn.
+		#[pallet::weight(< T as Config >::WeightInfo::governance_burn_liquid())]
+		#[transactional]
+		pub fn governance_burn_liquid(origin: OriginFor<T>, from: T::AccountId, amount: Balance) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			T::Currency::withdraw(T::LiquidCurrencyId::get(), &from, amount)?;
+			Self::deposit_event(Event::<T>::LiquidBurnedByGovernance(from, amount));
+			Ok(())
+		}
+
+		/// Sets the cap on the total issuance of the Liquid currency, or removes it if `None`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `cap`: The maximum total issuance of the Liquid currency.
+		#[pallet::weight(< T as Config >::WeightInfo::set_liquid_currency_issuance_cap())]
+		#[transactional]
+		pub fn set_liquid_currency_issuance_cap(origin: OriginFor<T>, cap: Option<Balance>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			LiquidCurrencyIssuanceCap::<T>::set(cap);
+			Self::deposit_event(Event::<T>::LiquidCurrencyIssuanceCapSet(cap));
+			Ok(())
+		}
+
+		/// Request to mint some Liquid currency on behalf of multiple users at once. The caller
+		/// pays the Staking currency for every request. If any of the requests fails, the whole
+		/// call is reverted.
+		///
+		/// Parameters:
+		/// - `requests`: The users to mint for, and the amount of Staking currency for each.
+		#[pallet::weight(< T as Config >::WeightInfo::request_mint().saturating_mul(requests.len() as Weight))]
+		#[transactional]
+		pub fn request_mint_batch(origin: OriginFor<T>, requests: Vec<(T::AccountId, Balance)>) -> DispatchResult {
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+
+			let who = ensure_signed(origin)?;
+			ensure!(
+				requests.len() as u32 <= T::MaxMintRequestsPerCall::get(),
+				Error::<T>::TooManyMintRequests
+			);
+			Self::update_last_mint_block(&who)?;
+
+			for (beneficiary, amount) in requests {
+				Self::do_request_mint(&who, &stash_account, beneficiary, amount, Default::default())?;
+			}
+			Ok(())
+		}
+
+		/// Request to mint some Liquid currency as a pool, on behalf of its depositors. The pool
+		/// pays the sum of the shares, and holds the pending amount. When it is claimed, each
+		/// depositor receives the Liquid currency minted for their share.
+		///
+		/// Parameters:
+		/// - `shares`: The depositors, and the amount of Staking currency for each.
+		#[pallet::weight(< T as Config >::WeightInfo::request_mint()
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(shares.len() as Weight)))]
+		#[transactional]
+		pub fn request_mint_pooled(origin: OriginFor<T>, shares: Vec<(T::AccountId, Balance)>) -> DispatchResult {
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+
+			let pool = ensure_signed(origin)?;
+			let current_batch = Self::current_batch();
+			let depositors = PooledShares::<T>::iter_prefix((current_batch, pool.clone()))
+				.count()
+				.saturating_add(UnconfirmedPooledShares::<T>::iter_prefix((current_batch, pool.clone())).count());
+			ensure!(
+				depositors.saturating_add(shares.len()) as u32 <= T::MaxMintRequestsPerCall::get(),
+				Error::<T>::TooManyMintRequests
+			);
+			Self::update_last_mint_block(&pool)?;
+
+			let total = shares
+				.iter()
+				.try_fold(0, |total: Balance, (_, share)| total.checked_add(*share))
+				.ok_or(ArithmeticError::Overflow)?;
+			Self::ensure_not_paused()?;
+			T::Currency::transfer(T::StakingCurrencyId::get(), &pool, &stash_account, total)?;
+			// Large pooled mints are held with their shares, until Governance confirms them.
+			let held = Self::hold_or_add_pending_mint(pool.clone(), total, Default::default())?;
+			for (depositor, share) in shares.iter() {
+				Self::ensure_not_blocked(depositor)?;
+				let add_share = |current: &mut Balance| *current = current.saturating_add(*share);
+				if held {
+					UnconfirmedPooledShares::<T>::mutate((current_batch, pool.clone()), depositor, add_share);
+				} else {
+					PooledShares::<T>::mutate((current_batch, pool.clone()), depositor, add_share);
+				}
+			}
+
+			Self::deposit_event(Event::<T>::PooledMintRequested(
+				current_batch,
+				pool,
+				shares.len() as u32,
+				total,
+			));
+			Ok(())
+		}
+
+		/// Sets the threshold above which mints must be confirmed by Governance, or disables
+		/// confirmation if `None`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `threshold`: The largest amount of Staking currency that can be minted without
+		///   confirmation.
+		#[pallet::weight(< T as Config >::WeightInfo::set_large_mint_threshold())]
+		#[transactional]
+		pub fn set_large_mint_threshold(origin: OriginFor<T>, threshold: Option<Balance>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			LargeMintThreshold::<T>::set(threshold);
+			Self::deposit_event(Event::<T>::LargeMintThresholdSet(threshold));
+			Ok(())
+		}
+
+		/// Sets the maximum change in the Staking currency total from one issue to the next, or
+		/// removes it if `None`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `max_change`: The largest allowed change, as a ratio of the previous batch's total.
+		#[pallet::weight(< T as Config >::WeightInfo::set_max_staking_total_change())]
+		#[transactional]
+		pub fn set_max_staking_total_change(origin: OriginFor<T>, max_change: Option<Ratio>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			MaxStakingTotalChange::<T>::set(max_change);
+			Self::deposit_event(Event::<T>::MaxStakingTotalChangeSet(max_change));
+			Ok(())
+		}
+
+		/// Confirms a large mint, adding it to the user's pending amount in the current batch. A
+		/// pool's depositors' shares are moved with it.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `who`: The user who requested the mint.
+		/// - `batch`: The batch the mint was requested in.
+		#[pallet::weight(< T as Config >::WeightInfo::confirm_large_mint()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(T::MaxMintRequestsPerCall::get() as Weight))
+			.saturating_add(T::DbWeight::get().reads_writes(4, 4)))]
+		#[transactional]
+		pub fn confirm_large_mint(origin: OriginFor<T>, who: T::AccountId, batch: BatchIndex) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			let amount = UnconfirmedMints::<T>::take(batch, &who);
+			ensure!(amount != 0, Error::<T>::NoUnconfirmedMint);
+			TotalUnconfirmedMints::<T>::mutate(|total| *total = total.saturating_sub(amount));
+			Self::add_pending_mint(who.clone(), amount, Default::default())?;
+			let current_batch = Self::current_batch();
+			let restaked = UnconfirmedRestaked::<T>::take(batch, &who);
+			if !restaked.is_zero() {
+				TotalUnconfirmedRestaked::<T>::mutate(|total| *total = total.saturating_sub(restaked));
+				Self::add_restaked(&who, current_batch, restaked);
+			}
+			for (depositor, share) in UnconfirmedPooledShares::<T>::drain_prefix((batch, who.clone())) {
+				PooledShares::<T>::mutate((current_batch, who.clone()), depositor, |current| {
+					*current = current.saturating_add(share)
+				});
+			}
+
+			Self::deposit_event(Event::<T>::LargeMintConfirmed(batch, current_batch, who, amount));
+			Ok(())
+		}
+
+		/// Rejects a large mint, refunding the Staking currency from the relay chain stash account
+		/// to the user the mint is held for. Any restaked part is refunded in Liquid currency, see
+		/// `refund_pending`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `who`: The user the mint is held for.
+		/// - `batch`: The batch the mint was requested in.
+		#[pallet::weight(< T as Config >::WeightInfo::reject_large_mint()
+			.saturating_add(T::DbWeight::get().reads_writes(4, 4)))]
+		#[transactional]
+		pub fn reject_large_mint(origin: OriginFor<T>, who: T::AccountId, batch: BatchIndex) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+
+			let amount = UnconfirmedMints::<T>::take(batch, &who);
+			ensure!(amount != 0, Error::<T>::NoUnconfirmedMint);
+			TotalUnconfirmedMints::<T>::mutate(|total| *total = total.saturating_sub(amount));
+			UnconfirmedPooledShares::<T>::remove_prefix((batch, who.clone()), None);
+			let restaked = UnconfirmedRestaked::<T>::take(batch, &who);
+			TotalUnconfirmedRestaked::<T>::mutate(|total| *total = total.saturating_sub(restaked));
+			Self::refund_pending(&stash_account, &who, amount.saturating_sub(restaked), restaked)?;
+
+			Self::deposit_event(Event::<T>::LargeMintRejected(batch, who, amount));
+			Ok(())
+		}
+
+		/// Move the caller's pending amount from an unprocessed batch into the current batch.
+		/// Useful when a batch is experiencing issues and will not be processed.
+		///
+		/// Parameters:
+		/// - `from_batch`: The unprocessed batch the pending amount is moved from.
+		#[pallet::weight(< T as Config >::WeightInfo::reroll_pending()
+			.saturating_add(T::DbWeight::get().reads_writes(4, 4)))]
+		#[transactional]
+		pub fn reroll_pending(origin: OriginFor<T>, from_batch: BatchIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let current_batch = Self::current_batch();
+			ensure!(from_batch != current_batch, Error::<T>::CannotRerollIntoSameBatch);
+			ensure!(
+				Self::batch_total_issuance_info(from_batch).is_none(),
+				Error::<T>::BatchAlreadyProcessed
+			);
+
+			let amount = PendingAmount::<T>::take(&from_batch, &who);
+			ensure!(amount != 0, Error::<T>::NoPendingAmount);
+			BatchPendingTotal::<T>::mutate(from_batch, |total| *total = total.saturating_sub(amount));
+			TotalPendingStaking::<T>::mutate(|total| *total = total.saturating_sub(amount));
+			// A slot is freed before the increase, so moving never exceeds the batches cap.
+			PendingBatches::<T>::mutate(&who, |batches| batches.retain(|b| *b != from_batch));
+
+			Self::increase_pending(&who, current_batch, amount)?;
+			let restaked = Self::take_restaked(&who, from_batch);
+			if !restaked.is_zero() {
+				Self::add_restaked(&who, current_batch, restaked);
+			}
+			// A pool's depositors move with it.
+			for (depositor, share) in PooledShares::<T>::drain_prefix((from_batch, who.clone())) {
+				PooledShares::<T>::mutate((current_batch, who.clone()), depositor, |current| {
+					*current = current.saturating_add(share)
+				});
+			}
+
+			Self::deposit_event(Event::<T>::PendingRerolled(from_batch, current_batch, who, amount));
+			Ok(())
+		}
+
+		/// Refunds the Staking currency of up to `max` users with pending amounts in an
+		/// unprocessed batch, from the relay chain stash account. Restaked parts are refunded in
+		/// Liquid currency, see `refund_pending`. Meant as a last resort for a batch that can never
+		/// be issued. Call repeatedly to refund the whole batch.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `batch`: The unprocessed batch to refund.
+		/// - `max`: The maximum number of users refunded by this call.
+		#[pallet::weight(< T as Config >::WeightInfo::force_refund_batch(*max)
+			.saturating_add(T::DbWeight::get().reads_writes(3, 3).saturating_mul(*max as Weight)))]
+		#[transactional]
+		pub fn force_refund_batch(origin: OriginFor<T>, batch: BatchIndex, max: u32) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::batch_total_issuance_info(batch).is_none(),
+				Error::<T>::BatchAlreadyProcessed
+			);
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+
+			let refunds: Vec<(T::AccountId, Balance)> =
+				PendingAmount::<T>::iter_prefix(batch).take(max as usize).collect();
+			let mut total_refunded: Balance = 0;
+			for (who, amount) in refunds.iter() {
+				let restaked = Self::take_restaked(who, batch);
+				Self::refund_pending(&stash_account, who, amount.saturating_sub(restaked), restaked)?;
+				PendingAmount::<T>::remove(batch, who);
+				PendingBatches::<T>::mutate(who, |batches| batches.retain(|b| *b != batch));
+				Self::record_settlement(batch, who, SettlementKind::Refunded);
+				// Pools are refunded in full, and are responsible for refunding their depositors.
+				PooledShares::<T>::drain_prefix((batch, who.clone())).for_each(drop);
+				total_refunded = total_refunded.saturating_add(*amount);
+			}
+
+			BatchPendingTotal::<T>::mutate(batch, |total| *total = total.saturating_sub(total_refunded));
+			TotalPendingStaking::<T>::mutate(|total| *total = total.saturating_sub(total_refunded));
+
+			let refunded = refunds.len() as u32;
+			Self::deposit_event(Event::<T>::BatchForceRefunded(batch, refunded, total_refunded));
+			Ok(Some(
+				T::WeightInfo::force_refund_batch(refunded)
+					.saturating_add(T::DbWeight::get().reads_writes(3, 3).saturating_mul(refunded as Weight)),
+			)
+			.into())
+		}
+
+		/// Compares the relay chain stash account's balance with the pending amounts of the
+		/// unprocessed batches, and emits `ReconciliationMismatch` if the stash is short by more than
+		/// `T::ReconciliationTolerance`.
+		/// Requires `T::GovernanceOrigin`
+		#[pallet::weight(< T as Config >::WeightInfo::reconcile_stash()
+			.saturating_add(T::DbWeight::get().reads((T::MaxBatchSplits::get() as Weight).saturating_add(1).saturating_mul(2))))]
+		#[transactional]
+		pub fn reconcile_stash(origin: OriginFor<T>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			let (expected, actual) = Self::reconcile()?;
+			if actual.saturating_add(T::ReconciliationTolerance::get()) < expected {
+				Self::deposit_event(Event::<T>::ReconciliationMismatch(expected, actual));
+			}
+			Ok(())
+		}
+
+		/// Corrects a user's pending amount in the current batch, or one split off it. The difference is
+		/// transferred from the user to the relay chain stash account if the amount increases,
+		/// or refunded to the user if it decreases, from the stash account first and then from any
+		/// restaked part, see `refund_pending`.
+		/// Requires `T::GovernanceOrigin`
+		///
+		/// Parameters:
+		/// - `who`: The user whose pending amount is corrected.
+		/// - `batch`: The current batch, or a batch split off it, the pending amount is in.
+		/// - `new_amount`: The corrected pending amount.
+		#[pallet::weight(< T as Config >::WeightInfo::adjust_pending()
+			.saturating_add(T::DbWeight::get().reads_writes(3, 3)))]
+		#[transactional]
+		pub fn adjust_pending(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			batch: BatchIndex,
+			new_amount: Balance,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::batch_total_issuance_info(batch).is_none(),
+				Error::<T>::BatchAlreadyProcessed
+			);
+			ensure!(Self::is_open_batch(batch), Error::<T>::BatchNotOpen);
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+
+			let old_amount = Self::pending_amount(&batch, &who);
+			// TODO: Cross-chain transfer to and from the relay chain via XCM
+			if new_amount > old_amount {
+				T::Currency::transfer(
+					T::StakingCurrencyId::get(),
+					&who,
+					&stash_account,
+					new_amount - old_amount,
+				)?;
+			} else if new_amount < old_amount {
+				// Only the deposited part is in the stash account.
+				let restaked = Self::take_restaked(&who, batch);
+				let decrease = old_amount - new_amount;
+				let from_stash = decrease.min(old_amount.saturating_sub(restaked));
+				let from_restaked = decrease - from_stash;
+				if restaked > from_restaked {
+					Self::add_restaked(&who, batch, restaked - from_restaked);
+				}
+				Self::refund_pending(&stash_account, &who, from_stash, from_restaked)?;
+			}
+
+			// A pool's depositors' shares are scaled with its pending amount, rounding down, so they
+			// never add up to more than it.
+			if new_amount != old_amount {
+				let shares: Vec<(T::AccountId, Balance)> =
+					PooledShares::<T>::drain_prefix((batch, who.clone())).collect();
+				for (depositor, share) in shares {
+					let share = multiply_by_rational(share, new_amount, old_amount).unwrap_or_default();
+					if !share.is_zero() {
+						PooledShares::<T>::insert((batch, who.clone()), depositor, share);
+					}
+				}
+			}
+
+			if new_amount == 0 {
+				PendingAmount::<T>::remove(batch, &who);
+				PendingBatches::<T>::mutate(&who, |batches| batches.retain(|b| *b != batch));
+				if old_amount != 0 {
+					Self::record_settlement(batch, &who, SettlementKind::Cancelled);
+				}
+			} else {
+				SettledMints::<T>::remove(batch, &who);
+				PendingAmount::<T>::insert(batch, &who, new_amount);
+				PendingBatches::<T>::try_mutate(&who, |batches| -> DispatchResult {
+					if !batches.contains(&batch) {
+						batches.try_push(batch).map_err(|_| Error::<T>::TooManyPendingBatches)?;
+					}
+					Ok(())
+				})?;
+			}
+			BatchPendingTotal::<T>::mutate(batch, |total| {
+				*total = total.saturating_add(new_amount).saturating_sub(old_amount)
+			});
+			TotalPendingStaking::<T>::mutate(|total| {
+				*total = total.saturating_add(new_amount).saturating_sub(old_amount)
+			});
+
+			Self::deposit_event(Event::<T>::PendingAdjusted(batch, who, old_amount, new_amount));
+			Ok(())
+		}
+
+		/// Splits the current batch, so new mints go into a new, smaller batch. The pending
+		/// amounts of the old batch are kept, and can be claimed separately once processed.
+		/// Requires `T::GovernanceOrigin`
+		#[pallet::weight(< T as Config >::WeightInfo::split_current_batch())]
+		#[transactional]
+		pub fn split_current_batch(origin: OriginFor<T>) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			let current_batch = Self::current_batch();
+			SplitBatches::<T>::try_mutate(|batches| batches.try_push(current_batch))
+				.map_err(|_| Error::<T>::TooManyBatchSplits)?;
+
+			let new_batch = current_batch.checked_add(1).expect("Batch Index should not overflow.");
+			CurrentBatch::<T>::put(new_batch);
+			Self::deposit_event(Event::<T>::BatchSplit(current_batch, new_batch));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Mints the Liquid currency owed to `who` for their pending amount in a processed batch,
+		/// applying their `AutoCompound` and `AutoDeposit` preferences.
+		/// Returns the amount of Liquid currency minted.
+		fn do_claim(who: T::AccountId, batch: BatchIndex) -> Result<Balance, DispatchError> {
+			Self::claim_liquid(who, batch, true)
+		}
+
+		/// Mints the Liquid currency owed to `who` for their pending amount in a processed batch.
+		/// The user's `AutoCompound` and `AutoDeposit` preferences are only applied if
+		/// `apply_preferences` is set. Returns the amount of Liquid currency minted.
+		fn claim_liquid(
+			who: T::AccountId,
+			batch: BatchIndex,
+			apply_preferences: bool,
+		) -> Result<Balance, DispatchError> {
+			Self::ensure_not_paused()?;
+			Self::ensure_not_blocked(&who)?;
+			let staked_amount = Self::pending_amount(&batch, &who);
+			let total_info =
+				Self::effective_issuance_info(batch).ok_or(Error::<T>::LiquidCurrencyNotIssuedForThisBatch)?;
+			// Nothing to claim, so nothing is recorded.
+			if staked_amount.is_zero() {
+				return Ok(0);
+			}
+
+			// liquid_to_mint = staked_amount * liquid_total / staked_total
+			let rate = total_info.exchange_rate().ok_or(ArithmeticError::Overflow)?;
+			let liquid_to_mint = staking_to_liquid(staked_amount, rate).ok_or(ArithmeticError::Overflow)?;
+			ensure!(!total_info.liquid_total.is_zero(), Error::<T>::ZeroExchangeRate);
+
+			// The sum of all claims for a batch can never exceed the batch's liquid total.
+			ClaimedLiquid::<T>::try_mutate(batch, |claimed| -> DispatchResult {
+				let new_claimed = claimed.saturating_add(liquid_to_mint);
+				ensure!(new_claimed <= total_info.liquid_total, Error::<T>::BatchLiquidExhausted);
+				*claimed = new_claimed;
+				Ok(())
+			})?;
+
+			if let Some(cap) = Self::liquid_currency_issuance_cap() {
+				let new_issuance =
+					T::Currency::total_issuance(T::LiquidCurrencyId::get()).saturating_add(liquid_to_mint);
+				ensure!(new_issuance <= cap, Error::<T>::ExceededLiquidIssuanceCap);
+			}
+
+			// Mint the liquid currency into the user's account, or the mint router if one is set.
+			let router = Self::mint_router();
+			let destination = router.clone().unwrap_or_else(|| who.clone());
+			// If `who` is a pool, its depositors receive the Liquid currency minted for their
+			// shares, and the pool receives the rest.
+			let mut pool_liquid = liquid_to_mint;
+			for (depositor, share) in PooledShares::<T>::drain_prefix((batch, who.clone())) {
+				let depositor_liquid = staking_to_liquid(share, rate).ok_or(ArithmeticError::Overflow)?;
+				pool_liquid = pool_liquid
+					.checked_sub(depositor_liquid)
+					.ok_or(ArithmeticError::Underflow)?;
+				let depositor_destination = router.clone().unwrap_or_else(|| depositor.clone());
+				T::Currency::deposit(T::LiquidCurrencyId::get(), &depositor_destination, depositor_liquid)?;
+				Self::deposit_event(Event::<T>::PooledLiquidClaimed(
+					batch,
+					who.clone(),
+					depositor,
+					depositor_liquid,
+				));
+			}
+			T::Currency::deposit(T::LiquidCurrencyId::get(), &destination, pool_liquid)?;
+			// Pay the promotional bonus, if one is active.
+			let bonus = Self::current_mint_bonus()
+				.map(|bonus| bonus.saturating_mul_int(liquid_to_mint))
+				.unwrap_or_default();
+			if bonus != 0 {
+				let incentive_account = T::IncentiveAccount::get();
+				ensure!(
+					T::Currency::free_balance(T::LiquidCurrencyId::get(), &incentive_account) >= bonus,
+					Error::<T>::InsufficientIncentiveFunds
+				);
+				T::Currency::transfer(T::LiquidCurrencyId::get(), &incentive_account, &destination, bonus)?;
+			}
+			// Remove the pending request from storage
+			PendingAmount::<T>::remove(&batch, &who);
+			Self::take_restaked(&who, batch);
+			Self::record_settlement(batch, &who, SettlementKind::Claimed);
+			BatchPendingTotal::<T>::mutate(batch, |total| *total = total.saturating_sub(staked_amount));
+			TotalPendingStaking::<T>::mutate(|total| *total = total.saturating_sub(staked_amount));
+			PendingBatches::<T>::mutate(&who, |batches| batches.retain(|b| *b != batch));
+
+			// Apply the user's preferences. Routed claims are not in the user's account, so are
+			// left to the router.
+			let apply_preferences = apply_preferences && router.is_none();
+			// Restake a fraction of the minted Liquid currency into the current batch, the same
+			// way `claim_and_restake` does. The bonus is not restaked.
+			let mut compounded_liquid: Balance = 0;
+			if apply_preferences && Self::auto_compound(&who) {
+				compounded_liquid = T::AutoCompoundRatio::get().saturating_mul_int(pool_liquid);
+				let restake_amount = liquid_to_staking(compounded_liquid, rate).ok_or(ArithmeticError::Overflow)?;
+				if restake_amount.is_zero() {
+					compounded_liquid = 0;
+				} else if with_transaction_result(|| {
+					T::Currency::withdraw(T::LiquidCurrencyId::get(), &who, compounded_liquid)?;
+					Self::restake(who.clone(), restake_amount)
+				})
+				.is_ok()
+				{
+					Self::deposit_event(Event::<T>::AutoCompounded(
+						batch,
+						who.clone(),
+						compounded_liquid,
+						restake_amount,
+					));
+				} else {
+					// The claim still succeeds, with the Liquid currency left to the user.
+					Self::deposit_event(Event::<T>::AutoCompoundFailed(batch, who.clone(), compounded_liquid));
+					compounded_liquid = 0;
+				}
+			}
+			// Move the rest into the user's savings position if they opted in.
+			let auto_deposited = pool_liquid.saturating_add(bonus).saturating_sub(compounded_liquid);
+			let auto_deposited = if apply_preferences && Self::auto_deposit(&who) && !auto_deposited.is_zero() {
+				T::ClaimDepositHandler::on_claim_deposit(&who, auto_deposited)?
+			} else {
+				0
+			};
+
+			let total_claimed = liquid_to_mint.saturating_add(bonus);
+			ClaimHistory::<T>::mutate(&who, |history| {
+				// Drop the oldest claim to make room, once the history is full.
+				if history.len() as u32 >= T::MaxClaimHistory::get() && !history.is_empty() {
+					history.remove(0);
+				}
+				let _ = history.try_push((batch, total_claimed, rate));
+			});
+			if let Some(router) = router {
+				Self::deposit_event(Event::<T>::MintRouted(batch, who.clone(), router, total_claimed));
+			}
+			if !auto_deposited.is_zero() {
+				Self::deposit_event(Event::<T>::ClaimAutoDeposited(batch, who.clone(), auto_deposited));
+			}
+			Self::deposit_event(Event::<T>::LiquidCurrencyClaimed(batch, who, total_claimed));
+
+			Ok(total_claimed)
+		}
+
+		/// Claims at most `max` pending amounts of processed batches, resuming from
+		/// `AutoClaimCursor`, and returns the weight consumed. Pending amounts that fail to be
+		/// claimed are skipped, and can still be claimed manually. Nothing is claimed while claims
+		/// are limited to their owners, see `PermissionlessClaim`.
+		fn process_auto_claims(max: u32) -> Weight {
+			let mut weight = T::DbWeight::get().reads(3);
+			let (batch, start_key) = match Self::auto_claim_cursor() {
+				Some(cursor) if max != 0 && !Self::is_paused() && Self::permissionless_claim() => cursor,
+				_ => return weight,
+			};
+
+			let mut iterator = <PendingAmount<T> as IterableStorageDoubleMapExtended<_, _, _>>::iter_prefix(
+				batch,
+				Some(max),
+				start_key,
+			);
+			let users: Vec<T::AccountId> = iterator.by_ref().map(|(who, _)| who).collect();
+			for who in users {
+				let _ = with_transaction_result(|| Self::do_claim(who, batch).map(|_| ()));
+				weight = weight.saturating_add(T::WeightInfo::claim());
+			}
+
+			// Once the batch is done, move on to the next processed batch, if any.
+			if !iterator.finished {
+				AutoClaimCursor::<T>::put((batch, Some(iterator.map_iterator.previous_key)));
+			} else {
+				match batch.checked_add(1) {
+					Some(next_batch) if next_batch < Self::current_batch() => {
+						AutoClaimCursor::<T>::put((next_batch, Option::<Vec<u8>>::None))
+					}
+					_ => AutoClaimCursor::<T>::kill(),
+				}
+			}
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
+
+		/// Get the account ID of this pallet, derived from the `PalletId`.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		/// The mint bonus ratio, if a bonus is active at the current block.
+		pub fn current_mint_bonus() -> Option<Ratio> {
+			Self::mint_bonus().and_then(|(bonus, until_block)| {
+				if frame_system::Pallet::<T>::block_number() <= until_block {
+					Some(bonus)
+				} else {
+					None
+				}
+			})
+		}
+
+		/// Returns the Staking currency expected in the relay chain stash account, and its actual
+		/// balance. Only the current batch and the batches split off it are unprocessed, so only
+		/// their pending amounts are expected to be in the stash; processed batches are staked on
+		/// the relay chain, as are restaked pending amounts.
+		pub fn reconcile() -> Result<(Balance, Balance), DispatchError> {
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+			let expected = Self::unprocessed_pending_total();
+			let actual = T::Currency::free_balance(T::StakingCurrencyId::get(), &stash_account);
+			Ok((expected, actual))
+		}
+
+		/// The Staking currency expected in the relay chain stash account that is not staked yet:
+		/// the pending amounts of the unprocessed batches, and all mints held for confirmation,
+		/// whichever batch they were requested in, without their restaked parts.
+		pub fn staking_in_transit() -> Balance {
+			Self::unprocessed_pending_total()
+				.saturating_add(Self::total_unconfirmed_mints().saturating_sub(Self::total_unconfirmed_restaked()))
+		}
+
+		/// The pending totals of the current batch and the batches split off it, without their
+		/// restaked parts. Reads two totals per batch, so it is bounded by `T::MaxBatchSplits`.
+		fn unprocessed_pending_total() -> Balance {
+			Self::split_batches()
+				.into_inner()
+				.into_iter()
+				.chain(sp_std::iter::once(Self::current_batch()))
+				.fold(0, |total: Balance, batch| {
+					total.saturating_add(
+						Self::batch_pending_total(batch).saturating_sub(Self::batch_restaked_total(batch)),
+					)
+				})
+		}
+
+		/// The user's unclaimed pending amounts, in ascending batch order.
+		pub fn pending_mints(who: &T::AccountId) -> Vec<(BatchIndex, Balance)> {
+			let mut batches = Self::pending_batches(who).into_inner();
+			batches.sort();
+			batches
+				.into_iter()
+				.map(|batch| (batch, Self::pending_amount(&batch, who)))
+				.collect()
+		}
+
+		/// The Liquid currency, including any active mint bonus, the user can claim now for their
+		/// pending amounts in processed batches. Pending amounts in unprocessed batches are not
+		/// counted.
+		pub fn total_claimable(who: &T::AccountId) -> Balance {
+			let bonus = Self::current_mint_bonus();
+			Self::pending_batches(who)
+				.into_inner()
+				.into_iter()
+				.filter_map(|batch| {
+					let info = Self::effective_issuance_info(batch)?;
+					let liquid = staking_to_liquid(Self::pending_amount(&batch, who), info.exchange_rate()?)?;
+					let bonus = bonus.map(|bonus| bonus.saturating_mul_int(liquid)).unwrap_or_default();
+					Some(liquid.saturating_add(bonus))
+				})
+				.fold(0, |total: Balance, liquid| total.saturating_add(liquid))
+		}
+
+		/// At most `limit` of the user's unclaimed pending amounts, in ascending batch order,
+		/// starting from `start_batch`. To get the next page, pass the last returned batch plus 1.
+		pub fn pending_mints_paged(
+			who: &T::AccountId,
+			start_batch: BatchIndex,
+			limit: u32,
+		) -> Vec<(BatchIndex, Balance)> {
+			Self::pending_mints(who)
+				.into_iter()
+				.filter(|(batch, _)| *batch >= start_batch)
+				.take(limit as usize)
+				.collect()
+		}
+
+		/// The user's pending amount in a batch, and the batch's exchange rate. The rate is zero if
+		/// the batch has not been processed.
+		pub fn export_pending(who: &T::AccountId, batch: BatchIndex) -> (Balance, Ratio) {
+			(
+				Self::pending_amount(batch, who),
+				Self::batch_exchange_rate(batch).unwrap_or_default(),
+			)
+		}
+
+		/// The recorded issuance of a processed batch, with its Liquid total scaled by the rebase
+		/// factor. Claims are always computed from this.
+		fn effective_issuance_info(batch: BatchIndex) -> Option<TotalIssuanceInfo> {
+			Self::batch_total_issuance_info(batch).map(|info| TotalIssuanceInfo {
+				staking_total: info.staking_total,
+				liquid_total: Self::rebase_factor().saturating_mul_int(info.liquid_total),
+			})
+		}
+
+		/// The Staking to Liquid exchange rate of a processed batch.
+		pub fn batch_exchange_rate(batch: BatchIndex) -> Option<Ratio> {
+			Self::batch_total_issuance_info(batch).and_then(|info| info.exchange_rate())
+		}
+
+		/// The Staking to Liquid exchange rate of the most recently processed batch. Only reads
+		/// the batches' recorded issuance, so it is accurate when queried at a historical block.
+		pub fn exchange_rate() -> Option<Ratio> {
+			(0..Self::current_batch().0)
+				.rev()
+				.find_map(|batch| Self::batch_exchange_rate(BatchIndex(batch)))
+		}
+
+		/// The exchange rate claims are currently computed at: that of the most recently processed
+		/// batch, scaled by the rebase factor.
+		pub fn effective_exchange_rate() -> Option<Ratio> {
+			Self::exchange_rate().map(|rate| rate.saturating_mul(Self::rebase_factor()))
+		}
+
+		/// How much the Liquid currency the user has claimed from `since_batch` onwards, as
+		/// recorded in their claim history, has grown in value against the Staking currency, e.g.
+		/// 1.1 if it is worth 10% more than at the rates it was claimed at. Returns `None` if
+		/// `since_batch` or the last processed batch has no exchange rate, or the user has no
+		/// such claims.
+		pub fn realized_yield(who: &T::AccountId, since_batch: BatchIndex) -> Option<Ratio> {
+			Self::batch_exchange_rate(since_batch)?;
+			let current_rate = Self::effective_exchange_rate()?;
+			let (staked_value, liquid_claimed) = Self::claim_history(who)
+				.into_inner()
+				.into_iter()
+				.filter(|(batch, _, _)| *batch >= since_batch)
+				.try_fold((0, 0), |(staked, liquid): (Balance, Balance), (_, claimed, rate)| {
+					Some((
+						staked.saturating_add(liquid_to_staking(claimed, rate)?),
+						liquid.saturating_add(claimed),
+					))
+				})?;
+			let current_value = liquid_to_staking(liquid_claimed, current_rate)?;
+			Ratio::checked_from_rational(current_value, staked_value)
+		}
+
+		/// A summary of the module's current state.
+		pub fn protocol_status() -> ProtocolStatus {
+			ProtocolStatus {
+				current_batch: Self::current_batch(),
+				exchange_rate: Self::exchange_rate(),
+				total_pending_staking: Self::total_pending_staking(),
+				liquid_total_issuance: T::Currency::total_issuance(T::LiquidCurrencyId::get()),
+				paused: Self::is_paused(),
+			}
+		}
+
+		/// Of the `limit` batches from `start_batch` onwards, the processed ones that still have
+		/// unclaimed pending amounts, in ascending batch order. At most `limit` batches are read, so
+		/// to get the next page, pass `start_batch` plus `limit`.
+		pub fn claimable_batches(start_batch: BatchIndex, limit: u32) -> Vec<BatchIndex> {
+			let end_batch = start_batch.0.saturating_add(limit).min(Self::current_batch().0);
+			(start_batch.0..end_batch)
+				.map(BatchIndex)
+				.filter(|batch| {
+					BatchTotalIssuanceInfo::<T>::contains_key(batch)
+						&& PendingAmount::<T>::iter_prefix(batch).next().is_some()
+				})
+				.collect()
+		}
+
+		/// The Liquid currency, including any active mint bonus, the user would receive for their
+		/// pending amount in `batch` if it were issued now with the given `staking_total`. Does
+		/// not mutate storage. Returns 0 if the amount cannot be computed.
+		pub fn simulate_claim(who: &T::AccountId, batch: BatchIndex, staking_total: Balance) -> Balance {
+			// Scaled by the rebase factor, as claims are, see `effective_issuance_info`.
+			let liquid_to_mint = Self::issuance_info_for(staking_total)
+				.and_then(|info| {
+					TotalIssuanceInfo {
+						staking_total: info.staking_total,
+						liquid_total: Self::rebase_factor().saturating_mul_int(info.liquid_total),
+					}
+					.exchange_rate()
+				})
+				.and_then(|rate| staking_to_liquid(Self::pending_amount(&batch, who), rate))
+				.unwrap_or_default();
+			let bonus = Self::current_mint_bonus()
+				.map(|bonus| bonus.saturating_mul_int(liquid_to_mint))
+				.unwrap_or_default();
+			liquid_to_mint.saturating_add(bonus)
+		}
+
+		/// The issuance info the unprocessed batches would be processed with, if issued now with
+		/// the given `staking_total`. Returns `None` on overflow.
+		fn issuance_info_for(staking_total: Balance) -> Option<TotalIssuanceInfo> {
+			let mut liquid_total = T::Currency::total_issuance(T::LiquidCurrencyId::get());
+			if liquid_total.is_zero() {
+				liquid_total = staking_to_liquid(staking_total, T::InitialExchangeRate::get())?;
+			}
+			if Self::processed_batch_count() < T::BootstrapBatches::get() {
+				if let Some(bootstrap_rate) = T::BootstrapRateProvider::get() {
+					liquid_total = staking_to_liquid(staking_total, bootstrap_rate)?;
+				}
+			}
+			Some(TotalIssuanceInfo {
+				staking_total,
+				liquid_total,
+			})
+		}
+
+		/// Whether the batch is the current batch, or one split off it, so can still be processed.
+		fn is_open_batch(batch: BatchIndex) -> bool {
+			batch == Self::current_batch() || Self::split_batches().contains(&batch)
+		}
+
+		fn ensure_not_paused() -> DispatchResult {
+			ensure!(!Self::is_paused(), Error::<T>::ModulePaused);
+			Ok(())
+		}
+
+		fn ensure_not_blocked(who: &T::AccountId) -> DispatchResult {
+			ensure!(!Self::is_blocked(who), Error::<T>::AccountBlocked);
+			Ok(())
+		}
+
+		/// Ensures the user's mint cooldown has expired, and records the current block as the
+		/// user's last mint.
+		fn update_last_mint_block(who: &T::AccountId) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(last_mint_block) = Self::last_mint_block(who) {
+				ensure!(
+					now >= last_mint_block.saturating_add(T::MintCooldownBlocks::get()),
+					Error::<T>::MintCooldownActive
+				);
+			}
+			LastMintBlock::<T>::insert(who, now);
+			Ok(())
+		}
+
+		/// Processes the current batch, and any batches split off it, at the rate given by
+		/// `staking_total`. Unless `force` is set, fails if nothing is pending in them.
+		fn do_issue(staking_total: Balance, force: bool) -> DispatchResult {
+			Self::ensure_not_paused()?;
+			ensure!(staking_total != 0, Error::<T>::InvalidStakedCurrencyTotalIssuance);
+			let current_batch = Self::current_batch();
+			if let Some(max_change) = Self::max_staking_total_change() {
+				let first_batch = Self::split_batches().first().copied().unwrap_or(current_batch);
+				if first_batch > BatchIndex(0) {
+					if let Some(previous) = Self::batch_total_issuance_info(first_batch.saturating_sub(1)) {
+						let change = if staking_total > previous.staking_total {
+							staking_total.saturating_sub(previous.staking_total)
+						} else {
+							previous.staking_total.saturating_sub(staking_total)
+						};
+						ensure!(
+							change <= max_change.saturating_mul_int(previous.staking_total),
+							Error::<T>::StakingTotalChangeTooLarge
+						);
+					}
+				}
+			}
+			if !force {
+				let has_pending_mints = Self::split_batches()
+					.iter()
+					.chain(sp_std::iter::once(&current_batch))
+					.any(|batch| !Self::batch_pending_total(batch).is_zero());
+				ensure!(has_pending_mints, Error::<T>::NoPendingMints);
+			}
+
+			let total_for_batch = Self::issuance_info_for(staking_total).ok_or(ArithmeticError::Overflow)?;
+			let rate = total_for_batch.exchange_rate().ok_or(ArithmeticError::Overflow)?;
+			Self::process_current_batch(|_| total_for_batch.clone(), rate);
+			Ok(())
+		}
+
+		/// Processes the current batch, and any batches split off it, with the issuance info given
+		/// for each. `issue` and `InstantBatch` both close batches through this, so they report
+		/// the rate change, notify `T::BatchHandler`, prune settlements and auto-pause alike.
+		fn process_current_batch(issuance_info: impl Fn(BatchIndex) -> TotalIssuanceInfo, rate: Ratio) {
+			let current_batch = Self::current_batch();
+			let old_rate = Self::effective_exchange_rate();
+			let new_rate = Some(rate.saturating_mul(Self::rebase_factor()));
+			if old_rate != new_rate {
+				Self::deposit_event(Event::<T>::RateChanged(RateChangeCause::Issue, old_rate, new_rate));
+			}
+
+			// Batches split off the current batch share its exchange rate.
+			let mut batches = SplitBatches::<T>::take().into_inner();
+			batches.push(current_batch);
+			let first_batch = batches[0];
+			let previous_rate = first_batch
+				.0
+				.checked_sub(1)
+				.and_then(|previous| Self::batch_exchange_rate(BatchIndex(previous)));
+
+			let processed_before = Self::processed_batch_count();
+			ProcessedBatchCount::<T>::put(processed_before.saturating_add(batches.len() as u32));
+			let bootstrap_batches = T::BootstrapBatches::get();
+			let ends_bootstrap =
+				processed_before < bootstrap_batches && Self::processed_batch_count() >= bootstrap_batches;
+
+			for batch in batches {
+				let total_for_batch = issuance_info(batch);
+				BatchTotalIssuanceInfo::<T>::insert(&batch, total_for_batch.clone());
+				T::BatchHandler::on_batch_processed(batch, rate);
+				Self::deposit_batch_closed(batch);
+				Self::deposit_event(Event::<T>::BatchProcessed(
+					batch,
+					total_for_batch.staking_total,
+					total_for_batch.liquid_total,
+				));
+			}
+			CurrentBatch::<T>::put(current_batch.checked_add(1).expect("Batch Index should not overflow."));
+			Self::prune_settled_mints();
+			if Self::auto_claim_cursor().is_none() {
+				AutoClaimCursor::<T>::put((first_batch, Option::<Vec<u8>>::None));
+			}
+			if ends_bootstrap {
+				Self::deposit_event(Event::<T>::BootstrapEnded(current_batch));
+			}
+
+			// Pause if the rate deviates too far from the previous batch's.
+			if let Some(previous_rate) = previous_rate {
+				let deviation = if rate > previous_rate {
+					rate.saturating_sub(previous_rate)
+				} else {
+					previous_rate.saturating_sub(rate)
+				};
+				if deviation > previous_rate.saturating_mul(T::AutoPauseThreshold::get()) {
+					Paused::<T>::put(true);
+					Self::deposit_event(Event::<T>::AutoPaused(previous_rate, rate));
+				}
+			}
+		}
+
+		/// The weight of closing the current batch and the batches split off it, on top of
+		/// `WeightInfo::issue`: the pending totals and `BatchClosed` participants read for each
+		/// batch, and the settlements pruned.
+		fn close_batches_weight() -> Weight {
+			let batches = (T::MaxBatchSplits::get() as Weight).saturating_add(1);
+			let participants = (T::MaxParticipantsInEvent::get() as Weight).saturating_add(1);
+			T::DbWeight::get()
+				.reads(batches.saturating_mul(participants.saturating_add(1)))
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+				.saturating_add(T::DbWeight::get().writes(T::MaxSettledMintsPruned::get() as Weight))
+		}
+
+		/// Records how a pending amount was settled, unless the batch's settlements have already
+		/// been pruned.
+		fn record_settlement(batch: BatchIndex, who: &T::AccountId, kind: SettlementKind) {
+			if batch >= Self::settled_mints_prune_cursor() {
+				SettledMints::<T>::insert(batch, who, kind);
+			}
+		}
+
+		/// Prunes the settlements of batches processed at least `T::SettledMintsRetention` batches
+		/// ago, removing at most `T::MaxSettledMintsPruned` settlements. The rest are pruned the
+		/// next time batches are processed.
+		fn prune_settled_mints() {
+			let expired_before = Self::current_batch().0.saturating_sub(T::SettledMintsRetention::get());
+			let mut cursor = Self::settled_mints_prune_cursor();
+			let mut remaining = T::MaxSettledMintsPruned::get();
+			while cursor.0 < expired_before && remaining != 0 {
+				match SettledMints::<T>::remove_prefix(cursor, Some(remaining)) {
+					sp_io::KillStorageResult::AllRemoved(removed) => {
+						// Every batch visited counts towards the limit, even if it has no settlements.
+						remaining = remaining.saturating_sub(removed.max(1));
+						cursor = cursor.checked_add(1).expect("Batch Index should not overflow.");
+					}
+					sp_io::KillStorageResult::SomeRemaining(_) => break,
+				}
+			}
+			SettledMintsPruneCursor::<T>::put(cursor);
+		}
+
+		/// Emits `BatchClosed` for the batch. At most `T::MaxParticipantsInEvent` + 1 participants
+		/// are read.
+		fn deposit_batch_closed(batch: BatchIndex) {
+			let mut participants = BoundedVec::<T::AccountId, T::MaxParticipantsInEvent>::default();
+			let mut omitted = false;
+			for (who, _) in PendingAmount::<T>::iter_prefix(batch) {
+				if participants.try_push(who).is_err() {
+					participants = Default::default();
+					omitted = true;
+					break;
+				}
+			}
+			Self::deposit_event(Event::<T>::BatchClosed(batch, participants, omitted));
+		}
+
+		/// Processes the current batch, and any batches split off it, at the bootstrap rate if
+		/// there is one, or else at the most recent exchange rate, or `T::InitialExchangeRate` if
+		/// no batch has been processed yet. Used when `InstantBatch` is on. Does nothing if nothing
+		/// is pending in the current batch, e.g. because the mint is held for confirmation.
+		fn process_instant_batch() -> DispatchResult {
+			if Self::batch_pending_total(Self::current_batch()).is_zero() {
+				return Ok(());
+			}
+
+			let rate = match T::BootstrapRateProvider::get() {
+				Some(bootstrap_rate) if Self::processed_batch_count() < T::BootstrapBatches::get() => bootstrap_rate,
+				_ => Self::exchange_rate().unwrap_or_else(T::InitialExchangeRate::get),
+			};
+			// Each batch is issued for its own pending total. None of them can overflow if their
+			// sum does not.
+			let staking_total = Self::split_batches()
+				.iter()
+				.chain(sp_std::iter::once(&Self::current_batch()))
+				.fold(0, |total: Balance, batch| {
+					total.saturating_add(Self::batch_pending_total(batch))
+				});
+			staking_to_liquid(staking_total, rate).ok_or(ArithmeticError::Overflow)?;
+
+			Self::process_current_batch(
+				|batch| {
+					let staking_total = Self::batch_pending_total(batch);
+					TotalIssuanceInfo {
+						staking_total,
+						liquid_total: staking_to_liquid(staking_total, rate).unwrap_or_default(),
+					}
+				},
+				rate,
+			);
+			Ok(())
+		}
+
+		/// Requests a mint for the signed caller, processing the batch right away if
+		/// `InstantBatch` is on.
+		fn do_request_own_mint(
+			origin: OriginFor<T>,
+			amount: Balance,
+			memo: BoundedVec<u8, T::MaxMemoLen>,
+		) -> DispatchResult {
+			let stash_account = Self::relay_chain_stash_account().ok_or(Error::<T>::RelayChainStashAccountNotSet)?;
+
+			let who = ensure_signed(origin)?;
+			Self::update_last_mint_block(&who)?;
+			Self::do_request_mint(&who, &stash_account, who.clone(), amount, memo)?;
+			if Self::instant_batch() {
+				Self::process_instant_batch()?;
+			}
+			Ok(())
+		}
+
+		/// Transfers `amount` of Staking currency from the `payer` into the stash account, and
+		/// adds it to `who`'s pending amount in the current batch.
+		fn do_request_mint(
+			payer: &T::AccountId,
+			stash_account: &T::AccountId,
+			who: T::AccountId,
+			amount: Balance,
+			memo: BoundedVec<u8, T::MaxMemoLen>,
+		) -> DispatchResult {
+			Self::ensure_not_paused()?;
+			Self::ensure_not_blocked(payer)?;
+			Self::ensure_not_blocked(&who)?;
+
+			// TODO: Cross-chain transfer to the relay chain via XCM
+			T::Currency::transfer(T::StakingCurrencyId::get(), payer, stash_account, amount)?;
+			Self::hold_or_add_pending_mint(who, amount, memo).map(|_| ())
+		}
+
+		/// Holds the mint for confirmation by Governance if it is above the large mint threshold,
+		/// or else adds it to the user's pending amount in the current batch. Returns whether the
+		/// mint is held. The Staking currency must already have been moved to the relay chain.
+		fn hold_or_add_pending_mint(
+			who: T::AccountId,
+			amount: Balance,
+			memo: BoundedVec<u8, T::MaxMemoLen>,
+		) -> Result<bool, DispatchError> {
+			match Self::large_mint_threshold() {
+				Some(threshold) if amount > threshold => {
+					// Held until Governance confirms it, and does not count towards any batch.
+					let current_batch = Self::current_batch();
+					UnconfirmedMints::<T>::mutate(current_batch, &who, |current| {
+						*current = current.saturating_add(amount)
+					});
+					TotalUnconfirmedMints::<T>::mutate(|total| *total = total.saturating_add(amount));
+					Self::deposit_event(Event::<T>::LargeMintPending(current_batch, who, amount));
+					Ok(true)
+				}
+				_ => Self::add_pending_mint(who, amount, memo).map(|_| false),
+			}
+		}
+
+		/// Adds `amount` to the user's pending amount in the current batch. The Staking currency
+		/// must already have been moved to the relay chain.
+		fn add_pending_mint(who: T::AccountId, amount: Balance, memo: BoundedVec<u8, T::MaxMemoLen>) -> DispatchResult {
+			Self::ensure_not_blocked(&who)?;
+			let current_batch = Self::current_batch();
+			let total_pending = Self::increase_pending(&who, current_batch, amount)?;
+
+			Self::deposit_event(Event::<T>::MintRequested(
+				current_batch,
+				who,
+				amount,
+				total_pending,
+				memo,
+			));
+			Ok(())
+		}
+
+		/// Adds `amount` of Staking currency restaked from claimed Liquid currency to the user's
+		/// pending amount in the current batch, or holds it for confirmation if it is above the
+		/// large mint threshold. Its Staking currency is already staked on the relay chain, so it
+		/// is tracked apart from the Staking currency in the relay chain stash account.
+		fn restake(who: T::AccountId, amount: Balance) -> DispatchResult {
+			let current_batch = Self::current_batch();
+			if Self::hold_or_add_pending_mint(who.clone(), amount, Default::default())? {
+				UnconfirmedRestaked::<T>::mutate(current_batch, &who, |current| {
+					*current = current.saturating_add(amount)
+				});
+				TotalUnconfirmedRestaked::<T>::mutate(|total| *total = total.saturating_add(amount));
+			} else {
+				Self::add_restaked(&who, current_batch, amount);
+			}
+			Ok(())
+		}
+
+		/// Records `amount` of the user's pending amount in `batch` as restaked.
+		fn add_restaked(who: &T::AccountId, batch: BatchIndex, amount: Balance) {
+			RestakedPending::<T>::mutate(batch, who, |current| *current = current.saturating_add(amount));
+			BatchRestakedTotal::<T>::mutate(batch, |total| *total = total.saturating_add(amount));
+		}
+
+		/// Removes the restaked part of the user's pending amount in `batch`, and returns it.
+		fn take_restaked(who: &T::AccountId, batch: BatchIndex) -> Balance {
+			let restaked = RestakedPending::<T>::take(batch, who);
+			if !restaked.is_zero() {
+				BatchRestakedTotal::<T>::mutate(batch, |total| *total = total.saturating_sub(restaked));
+			}
+			restaked
+		}
+
+		/// Refunds `deposited` Staking currency from the relay chain stash account to the user, and
+		/// `restaked` Staking currency as Liquid currency minted at the current exchange rate. The
+		/// restaked Staking currency never reached the stash account, so refunding it from there
+		/// would pay out other users' deposits.
+		fn refund_pending(
+			stash_account: &T::AccountId,
+			who: &T::AccountId,
+			deposited: Balance,
+			restaked: Balance,
+		) -> DispatchResult {
+			if !deposited.is_zero() {
+				// TODO: Cross-chain transfer from the relay chain via XCM
+				T::Currency::transfer(T::StakingCurrencyId::get(), stash_account, who, deposited)?;
+			}
+			if !restaked.is_zero() {
+				let rate = Self::effective_exchange_rate().ok_or(Error::<T>::LiquidCurrencyNotIssuedForThisBatch)?;
+				let liquid = staking_to_liquid(restaked, rate).ok_or(ArithmeticError::Overflow)?;
+				T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid)?;
+			}
+			Ok(())
+		}
+
+		/// Adds `amount` to the user's pending amount in `batch`, and returns the new pending
+		/// amount. Fails with the specific cap the increase would exceed, if any.
+		fn increase_pending(who: &T::AccountId, batch: BatchIndex, amount: Balance) -> Result<Balance, DispatchError> {
+			let total_pending = Self::pending_amount(batch, who)
+				.checked_add(amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				total_pending <= T::MaxPendingPerUser::get(),
+				Error::<T>::ExceededUserPendingCap
+			);
+			let batch_total = Self::batch_pending_total(batch)
+				.checked_add(amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				batch_total <= T::MaxPendingPerBatch::get(),
+				Error::<T>::ExceededBatchPendingCap
+			);
+
+			// Adding to an existing batch is always allowed, only new batches count towards the cap.
+			PendingBatches::<T>::try_mutate(who, |batches| -> DispatchResult {
+				if !batches.contains(&batch) {
+					batches.try_push(batch).map_err(|_| Error::<T>::TooManyPendingBatches)?;
+				}
+				Ok(())
+			})?;
+
+			PendingAmount::<T>::insert(batch, who, total_pending);
+			// The user has a pending amount in the batch again.
+			SettledMints::<T>::remove(batch, who);
+			BatchPendingTotal::<T>::insert(batch, batch_total);
+			TotalPendingStaking::<T>::mutate(|total| *total = total.saturating_add(amount));
+			Ok(total_pending)
+		}
+	}
+}
+
+impl<T: Config> OnMigrate<T::AccountId, BatchIndex, Balance, Ratio> for Pallet<T> {
+	fn migrate_pending(who: &T::AccountId, batch: BatchIndex) -> Result<(Balance, Ratio), DispatchError> {
+		ensure!(Self::migration_enabled(), Error::<T>::MigrationNotEnabled);
+		let (amount, rate) = Self::export_pending(who, batch);
+		ensure!(amount != 0, Error::<T>::NoPendingAmount);
+
+		PendingAmount::<T>::remove(batch, who);
+		Self::take_restaked(who, batch);
+		PendingBatches::<T>::mutate(who, |batches| batches.retain(|b| *b != batch));
+		Self::record_settlement(batch, who, SettlementKind::Migrated);
+		// Pools are migrated in full, and are responsible for their depositors.
+		PooledShares::<T>::drain_prefix((batch, who.clone())).for_each(drop);
+		BatchPendingTotal::<T>::mutate(batch, |total| *total = total.saturating_sub(amount));
+		TotalPendingStaking::<T>::mutate(|total| *total = total.saturating_sub(amount));
+
+		Self::deposit_event(Event::<T>::PendingMigrated(batch, who.clone(), amount));
+		Ok((amount, rate))
 	}
 }
@@ -25,9 +25,13 @@ use frame_support::{ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
 use module_support::mocks::MockAddressMapping;
 use orml_traits::parameter_type_with_key;
-use primitives::{Amount, TokenSymbol};
+use primitives::{Amount, EraIndex, TokenSymbol};
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
+use sp_runtime::{
+	testing::Header,
+	traits::{Bounded, IdentityLookup},
+	AccountId32,
+};
 
 pub type AccountId = AccountId32;
 pub type BlockNumber = u64;
@@ -41,6 +45,8 @@ pub const RELAY_CHAIN_STASH: AccountId = AccountId32::new([11u8; 32]);
 pub const ROOT: AccountId = AccountId32::new([255u8; 32]);
 pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
 pub const BOB: AccountId = AccountId32::new([2u8; 32]);
+pub const INCENTIVE_ACCOUNT: AccountId = AccountId32::new([12u8; 32]);
+pub const SAVINGS_ACCOUNT: AccountId = AccountId32::new([13u8; 32]);
 pub const ACALA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const KSM: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
 pub const LKSM: CurrencyId = CurrencyId::Token(TokenSymbol::LKSM);
@@ -134,6 +140,119 @@ parameter_types! {
 	pub const StakingCurrencyId: CurrencyId = KSM;
 	pub const LiquidCurrencyId: CurrencyId = LKSM;
 	pub const HomaLitePalletId: PalletId = PalletId(*b"aca/hmlt");
+	pub const MaxPendingBatchesPerUser: u32 = 3;
+	pub static MintCooldownBlocks: BlockNumber = 0;
+	pub const MaxMintRequestsPerCall: u32 = 3;
+	pub static ProcessedBatches: Vec<(BatchIndex, Ratio)> = vec![];
+	pub const ReconciliationTolerance: Balance = 1_000_000_000;
+	pub const MaxMemoLen: u32 = 32;
+	pub static AutoPauseThreshold: Ratio = Ratio::max_value();
+	pub const IncentiveAccount: AccountId = INCENTIVE_ACCOUNT;
+	pub const MaxBatchSplits: u32 = 2;
+	pub const MaxAutoClaimsPerBlock: u32 = 3;
+	pub static InitialExchangeRate: Ratio = Ratio::saturating_from_rational(10, 1);
+	pub const MaxClaimHistory: u32 = 3;
+	pub static MaxPendingPerUser: Balance = Balance::max_value();
+	pub static MaxPendingPerBatch: Balance = Balance::max_value();
+	pub const SettledMintsRetention: u32 = 10;
+	pub static MaxSettledMintsPruned: u32 = 10;
+	pub static BootstrapRate: Option<Ratio> = None;
+	pub static BootstrapBatches: u32 = 0;
+	pub const MaxClaimsPerCall: u32 = 3;
+	pub static ClaimDeposits: Vec<(AccountId, Balance)> = vec![];
+	pub static ClaimDepositsAccepted: bool = true;
+	pub AutoCompoundRatio: Ratio = Ratio::saturating_from_rational(1, 2);
+	pub const MaxParticipantsInEvent: u32 = 2;
+	pub static CurrencyOps: u32 = 0;
+	pub static FailCurrencyOpAt: Option<u32> = None;
+}
+
+/// `Currencies`, except that the `FailCurrencyOpAt`th transfer, deposit, withdrawal or slash
+/// counted by `CurrencyOps` fails, to test that failed extrinsics are rolled back.
+pub struct MockCurrency;
+impl MockCurrency {
+	fn count_op() -> DispatchResult {
+		let op = CurrencyOps::get().saturating_add(1);
+		CurrencyOps::set(op);
+		if FailCurrencyOpAt::get() == Some(op) {
+			return Err(DispatchError::Other("MockCurrency failure"));
+		}
+		Ok(())
+	}
+}
+impl MultiCurrency<AccountId> for MockCurrency {
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+
+	fn minimum_balance(currency_id: CurrencyId) -> Balance {
+		<Currencies as MultiCurrency<AccountId>>::minimum_balance(currency_id)
+	}
+
+	fn total_issuance(currency_id: CurrencyId) -> Balance {
+		<Currencies as MultiCurrency<AccountId>>::total_issuance(currency_id)
+	}
+
+	fn total_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		<Currencies as MultiCurrency<AccountId>>::total_balance(currency_id, who)
+	}
+
+	fn free_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		<Currencies as MultiCurrency<AccountId>>::free_balance(currency_id, who)
+	}
+
+	fn ensure_can_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		<Currencies as MultiCurrency<AccountId>>::ensure_can_withdraw(currency_id, who, amount)
+	}
+
+	fn transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		Self::count_op()?;
+		<Currencies as MultiCurrency<AccountId>>::transfer(currency_id, from, to, amount)
+	}
+
+	fn deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		Self::count_op()?;
+		<Currencies as MultiCurrency<AccountId>>::deposit(currency_id, who, amount)
+	}
+
+	fn withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		Self::count_op()?;
+		<Currencies as MultiCurrency<AccountId>>::withdraw(currency_id, who, amount)
+	}
+
+	fn can_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> bool {
+		<Currencies as MultiCurrency<AccountId>>::can_slash(currency_id, who, amount)
+	}
+
+	fn slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> Balance {
+		if Self::count_op().is_err() {
+			return amount;
+		}
+		<Currencies as MultiCurrency<AccountId>>::slash(currency_id, who, amount)
+	}
+}
+
+pub struct MockBatchHandler;
+impl OnBatchProcessed<BatchIndex, Ratio> for MockBatchHandler {
+	fn on_batch_processed(batch: BatchIndex, rate: Ratio) {
+		let mut processed = ProcessedBatches::get();
+		processed.push((batch, rate));
+		ProcessedBatches::set(processed);
+	}
+}
+
+/// Moves the deposited Liquid currency into `SAVINGS_ACCOUNT`, unless deposits are turned off.
+pub struct MockClaimDepositHandler;
+impl OnClaimDeposit<AccountId, Balance> for MockClaimDepositHandler {
+	fn on_claim_deposit(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		if !ClaimDepositsAccepted::get() {
+			return Ok(0);
+		}
+		<Currencies as MultiCurrency<AccountId>>::transfer(LKSM, who, &SAVINGS_ACCOUNT, amount)?;
+		let mut deposits = ClaimDeposits::get();
+		deposits.push((who.clone(), amount));
+		ClaimDeposits::set(deposits);
+		Ok(amount)
+	}
 }
 ord_parameter_types! {
 	pub const Root: AccountId = ROOT;
@@ -142,12 +261,34 @@ ord_parameter_types! {
 impl Config for Runtime {
 	type Event = Event;
 	type WeightInfo = ();
-	type Currency = Currencies;
+	type Currency = MockCurrency;
 	type StakingCurrencyId = StakingCurrencyId;
 	type LiquidCurrencyId = LiquidCurrencyId;
 	type PalletId = HomaLitePalletId;
 	type IssuerOrigin = EnsureSignedBy<Root, AccountId>;
 	type GovernanceOrigin = EnsureSignedBy<Root, AccountId>;
+	type MaxPendingBatchesPerUser = MaxPendingBatchesPerUser;
+	type MintCooldownBlocks = MintCooldownBlocks;
+	type MaxMintRequestsPerCall = MaxMintRequestsPerCall;
+	type BatchHandler = MockBatchHandler;
+	type ReconciliationTolerance = ReconciliationTolerance;
+	type MaxMemoLen = MaxMemoLen;
+	type AutoPauseThreshold = AutoPauseThreshold;
+	type IncentiveAccount = IncentiveAccount;
+	type MaxBatchSplits = MaxBatchSplits;
+	type MaxAutoClaimsPerBlock = MaxAutoClaimsPerBlock;
+	type InitialExchangeRate = InitialExchangeRate;
+	type MaxClaimHistory = MaxClaimHistory;
+	type MaxPendingPerUser = MaxPendingPerUser;
+	type MaxPendingPerBatch = MaxPendingPerBatch;
+	type SettledMintsRetention = SettledMintsRetention;
+	type MaxSettledMintsPruned = MaxSettledMintsPruned;
+	type BootstrapRateProvider = BootstrapRate;
+	type BootstrapBatches = BootstrapBatches;
+	type MaxClaimsPerCall = MaxClaimsPerCall;
+	type ClaimDepositHandler = MockClaimDepositHandler;
+	type AutoCompoundRatio = AutoCompoundRatio;
+	type MaxParticipantsInEvent = MaxParticipantsInEvent;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -167,6 +308,25 @@ frame_support::construct_runtime!(
 	}
 );
 
+/// The number of blocks in an era of the `MockClock`.
+pub const BLOCKS_PER_ERA: BlockNumber = 10;
+
+/// A deterministic clock for testing era-dependent logic. Eras are `BLOCKS_PER_ERA` blocks long,
+/// starting from era 0 at block 0.
+pub struct MockClock;
+impl MockClock {
+	/// The era of the current block.
+	pub fn current_era() -> EraIndex {
+		(System::block_number() / BLOCKS_PER_ERA) as EraIndex
+	}
+
+	/// Moves to the first block of the era `n` eras after the current one.
+	pub fn advance_eras(n: EraIndex) {
+		let era = Self::current_era().saturating_add(n);
+		System::set_block_number(BlockNumber::from(era) * BLOCKS_PER_ERA);
+	}
+}
+
 pub struct ExtBuilder {
 	tokens_balances: Vec<(AccountId, CurrencyId, Balance)>,
 	native_balances: Vec<(AccountId, Balance)>,
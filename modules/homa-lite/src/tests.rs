@@ -21,12 +21,16 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use mock::{
-	dollar, Currencies, Event, ExtBuilder, HomaLite, Origin, Runtime, System, ACALA, ALICE, BOB, INITIAL_BALANCE, KSM,
-	LKSM, RELAY_CHAIN_STASH, ROOT,
+	dollar, AccountId, AutoPauseThreshold, BootstrapBatches, BootstrapRate, ClaimDeposits, ClaimDepositsAccepted,
+	Currencies, CurrencyOps, Event, ExtBuilder, FailCurrencyOpAt, HomaLite, InitialExchangeRate, MaxAutoClaimsPerBlock,
+	MaxMemoLen, MaxPendingPerBatch, MaxPendingPerUser, MaxSettledMintsPruned, MintCooldownBlocks, MockClock, Origin,
+	ProcessedBatches, ReconciliationTolerance, Runtime, System, ACALA, ALICE, BLOCKS_PER_ERA, BOB, INCENTIVE_ACCOUNT,
+	INITIAL_BALANCE, KSM, LKSM, RELAY_CHAIN_STASH, ROOT, SAVINGS_ACCOUNT,
 };
-use sp_runtime::{traits::BadOrigin, ArithmeticError};
+use sp_runtime::{traits::BadOrigin, ArithmeticError, DispatchError};
+use sp_std::convert::{TryFrom, TryInto};
 
 #[test]
 fn mock_initialize_token_works() {
@@ -59,6 +63,80 @@ fn set_relay_chain_stash_works() {
 	});
 }
 
+#[test]
+fn set_relay_chain_stash_migrates_funds_in_transit() {
+	ExtBuilder::default().build().execute_with(|| {
+		let new_stash = AccountId::new([14u8; 32]);
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Only the unprocessed batches' funds are still in the stash.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(2)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(3)));
+		assert_eq!(HomaLite::staking_in_transit(), dollar(5));
+
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), new_stash.clone()));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::StashFundsMigrated(
+				RELAY_CHAIN_STASH,
+				new_stash.clone(),
+				dollar(5)
+			))));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(1));
+		assert_eq!(Currencies::free_balance(KSM, &new_stash), dollar(5));
+
+		// The migrated funds can be refunded from the new stash.
+		assert_ok!(HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(1), 2));
+		assert_eq!(Currencies::free_balance(KSM, &new_stash), 0);
+		assert_eq!(Currencies::free_balance(KSM, &BOB), dollar(INITIAL_BALANCE));
+	});
+}
+
+#[test]
+fn set_relay_chain_stash_migrates_held_mints_of_processed_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		let new_stash = AccountId::new([14u8; 32]);
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_large_mint_threshold(
+			Origin::signed(ROOT),
+			Some(dollar(500))
+		));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::total_unconfirmed_mints(), dollar(1000));
+
+		// The mint held in the processed batch is still in the stash.
+		assert_eq!(HomaLite::staking_in_transit(), dollar(1000));
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), new_stash.clone()));
+		assert_eq!(Currencies::free_balance(KSM, &new_stash), dollar(1000));
+
+		assert_ok!(HomaLite::reject_large_mint(Origin::signed(ROOT), ALICE, BatchIndex(0)));
+		assert_eq!(HomaLite::total_unconfirmed_mints(), 0);
+		assert_eq!(Currencies::free_balance(KSM, &ALICE), dollar(INITIAL_BALANCE));
+	});
+}
+
+#[test]
+fn set_relay_chain_stash_rejects_invalid_accounts() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The pallet's own account cannot be used as the stash.
+		assert_noop!(
+			HomaLite::set_stash_account_id(Origin::signed(ROOT), HomaLite::account_id()),
+			Error::<Runtime>::InvalidStashAccount
+		);
+
+		// The default (zero) account cannot be used as the stash.
+		assert_noop!(
+			HomaLite::set_stash_account_id(Origin::signed(ROOT), AccountId::default()),
+			Error::<Runtime>::InvalidStashAccount
+		);
+
+		assert_eq!(HomaLite::relay_chain_stash_account(), None);
+	});
+}
+
 #[test]
 fn request_mint_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -77,7 +155,13 @@ fn request_mint_works() {
 		assert_eq!(PendingAmount::<Runtime>::get(&current_batch, &ALICE), amount);
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::MintRequested(current_batch, ALICE, amount))
+			Event::HomaLite(crate::Event::MintRequested(
+				current_batch,
+				ALICE,
+				amount,
+				amount,
+				Default::default()
+			))
 		);
 	});
 }
@@ -102,26 +186,81 @@ fn can_request_mint_more_than_once_in_an_batch() {
 		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::MintRequested(current_batch, ALICE, dollar(1000)))
+			Event::HomaLite(crate::Event::MintRequested(
+				current_batch,
+				ALICE,
+				dollar(1000),
+				dollar(1000),
+				Default::default()
+			))
 		);
 
 		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(500)));
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::MintRequested(current_batch, ALICE, dollar(500)))
+			Event::HomaLite(crate::Event::MintRequested(
+				current_batch,
+				ALICE,
+				dollar(500),
+				dollar(1500),
+				Default::default()
+			))
 		);
 
 		assert_eq!(PendingAmount::<Runtime>::get(&current_batch, &ALICE), dollar(1500));
 	});
 }
 
+#[test]
+fn request_mint_is_capped_by_max_pending_batches_per_user() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		// Participate in the maximum number of batches.
+		for _ in 0..2 {
+			assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+			assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(10000)));
+		}
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		// Adding to an existing batch is always allowed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_eq!(
+			HomaLite::pending_batches(&ALICE).into_inner(),
+			vec![BatchIndex(0), BatchIndex(1), BatchIndex(2)]
+		);
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(10000)));
+
+		// A new batch would exceed the cap.
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)),
+			Error::<Runtime>::TooManyPendingBatches
+		);
+
+		// Other users are unaffected.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1000)));
+
+		// Claiming a batch frees up a slot.
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(
+			HomaLite::pending_batches(&ALICE).into_inner(),
+			vec![BatchIndex(1), BatchIndex(2)]
+		);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_eq!(
+			HomaLite::pending_batches(&ALICE).into_inner(),
+			vec![BatchIndex(1), BatchIndex(2), BatchIndex(3)]
+		);
+	});
+}
+
 #[test]
 fn issue_works() {
 	ExtBuilder::default().build().execute_with(|| {
 		// Setup the relay chain's stash account.
 		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
 		let current_batch = HomaLite::current_batch();
-		assert_eq!(current_batch, 0);
+		assert_eq!(current_batch, BatchIndex(0));
 
 		let lksm_issuance = Currencies::total_issuance(LKSM);
 		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
@@ -130,25 +269,25 @@ fn issue_works() {
 		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(3000)));
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::BatchProcessed(0, dollar(3000), lksm_issuance))
+			Event::HomaLite(crate::Event::BatchProcessed(BatchIndex(0), dollar(3000), lksm_issuance))
 		);
 
 		assert_eq!(
-			BatchTotalIssuanceInfo::<Runtime>::get(0),
+			BatchTotalIssuanceInfo::<Runtime>::get(BatchIndex(0)),
 			Some(TotalIssuanceInfo {
 				staking_total: dollar(3000),
 				liquid_total: lksm_issuance,
 			})
 		);
-		assert_eq!(BatchTotalIssuanceInfo::<Runtime>::get(1), None);
-		assert_eq!(HomaLite::current_batch(), 1);
+		assert_eq!(BatchTotalIssuanceInfo::<Runtime>::get(BatchIndex(1)), None);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(1));
 
-		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1)));
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1)));
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::BatchProcessed(1, dollar(1), lksm_issuance))
+			Event::HomaLite(crate::Event::BatchProcessed(BatchIndex(1), dollar(1), lksm_issuance))
 		);
-		assert_eq!(HomaLite::current_batch(), 2);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(2));
 	});
 }
 
@@ -164,7 +303,174 @@ fn issue_can_handle_failed_cases() {
 		// Only Issuer Origin is allowed to make issue call.
 		assert_noop!(HomaLite::issue(Origin::signed(ALICE), 0), BadOrigin);
 
-		assert_eq!(HomaLite::current_batch(), 0);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(0));
+	});
+}
+
+#[test]
+fn issue_fails_without_pending_mints() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			HomaLite::issue(Origin::signed(ROOT), dollar(1000)),
+			Error::<Runtime>::NoPendingMints
+		);
+
+		// Pending mints in split batches are enough.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::current_batch(), BatchIndex(2));
+
+		// Issuers can still close empty batches on purpose.
+		assert_noop!(
+			HomaLite::issue(Origin::signed(ROOT), dollar(1000)),
+			Error::<Runtime>::NoPendingMints
+		);
+		assert_noop!(HomaLite::force_issue(Origin::signed(ALICE), dollar(1000)), BadOrigin);
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BatchProcessed(
+				BatchIndex(2),
+				dollar(1000),
+				Currencies::total_issuance(LKSM)
+			))
+		);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(3));
+	});
+}
+
+#[test]
+fn issue_lists_batch_participants_up_to_the_bound() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		let batch_closed = |batch: BatchIndex| {
+			System::events()
+				.into_iter()
+				.find_map(|record| match record.event {
+					Event::HomaLite(crate::Event::BatchClosed(closed, participants, omitted)) if closed == batch => {
+						Some((participants.into_inner(), omitted))
+					}
+					_ => None,
+				})
+				.unwrap()
+		};
+
+		// Under the bound, every participant is listed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let (mut participants, omitted) = batch_closed(BatchIndex(0));
+		participants.sort();
+		assert_eq!(participants, vec![ALICE, BOB]);
+		assert!(!omitted);
+
+		// Over the bound, the list is omitted.
+		assert_ok!(Currencies::deposit(KSM, &ROOT, dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ROOT), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(batch_closed(BatchIndex(1)), (vec![], true));
+	});
+}
+
+#[test]
+fn issue_rejects_large_staking_total_changes() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		// Only governance can set the maximum change.
+		assert_noop!(
+			HomaLite::set_max_staking_total_change(Origin::signed(ALICE), Some(Ratio::saturating_from_rational(1, 10))),
+			BadOrigin
+		);
+		assert_ok!(HomaLite::set_max_staking_total_change(
+			Origin::signed(ROOT),
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MaxStakingTotalChangeSet(Some(
+				Ratio::saturating_from_rational(1, 10)
+			)))
+		);
+
+		// The first issue has no previous total to compare against.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Changes of more than 10% are rejected, in either direction.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_noop!(
+			HomaLite::issue(Origin::signed(ROOT), dollar(1101)),
+			Error::<Runtime>::StakingTotalChangeTooLarge
+		);
+		assert_noop!(
+			HomaLite::issue(Origin::signed(ROOT), dollar(899)),
+			Error::<Runtime>::StakingTotalChangeTooLarge
+		);
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1100)));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(990)));
+
+		// Removing the maximum allows any change.
+		assert_ok!(HomaLite::set_max_staking_total_change(Origin::signed(ROOT), None));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(5000)));
+	});
+}
+
+#[test]
+fn failed_extrinsics_are_rolled_back() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_instant_batch(Origin::signed(ROOT), true));
+		let initial_dollar = dollar(INITIAL_BALANCE);
+
+		// The Staking currency is transferred and the batch processed, but minting the Liquid
+		// currency fails.
+		CurrencyOps::set(0);
+		FailCurrencyOpAt::set(Some(2));
+		assert_noop!(
+			HomaLite::request_mint_and_claim(Origin::signed(ALICE), dollar(1)),
+			DispatchError::Other("MockCurrency failure")
+		);
+		assert_eq!(CurrencyOps::get(), 2);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), 0);
+		assert_eq!(HomaLite::pending_batches(&ALICE).into_inner(), vec![]);
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(0)), 0);
+		assert_eq!(HomaLite::total_pending_staking(), 0);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(0));
+		assert_eq!(HomaLite::batch_total_issuance_info(BatchIndex(0)), None);
+		assert_eq!(Currencies::free_balance(KSM, &ALICE), initial_dollar);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), 0);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), 0);
+
+		// The first user's mint succeeds, but the second's transfer fails.
+		assert_ok!(HomaLite::set_instant_batch(Origin::signed(ROOT), false));
+		CurrencyOps::set(0);
+		assert_noop!(
+			HomaLite::request_mint_batch(Origin::signed(ALICE), vec![(ALICE, dollar(1)), (BOB, dollar(1))]),
+			DispatchError::Other("MockCurrency failure")
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), 0);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &BOB), 0);
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(0)), 0);
+		assert_eq!(HomaLite::total_pending_staking(), 0);
+		assert_eq!(Currencies::free_balance(KSM, &ALICE), initial_dollar);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), 0);
+
+		// Without the failure, both succeed.
+		FailCurrencyOpAt::set(None);
+		assert_ok!(HomaLite::request_mint_batch(
+			Origin::signed(ALICE),
+			vec![(ALICE, dollar(1)), (BOB, dollar(1))]
+		));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(2));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(2));
 	});
 }
 
@@ -184,24 +490,24 @@ fn claim_works() {
 
 		// Trying to claim without "issue" call will fail
 		assert_noop!(
-			HomaLite::claim(Origin::signed(ALICE), ALICE, 0),
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
 			Error::<Runtime>::LiquidCurrencyNotIssuedForThisBatch
 		);
 
 		assert_ok!(HomaLite::issue(Origin::signed(ROOT), ksm_issuance));
 
 		// Now that the liquid currency for batch 0 is issued, users can claim them.
-		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, 0));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(0, ALICE, alice_yield))
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, alice_yield))
 		);
 		assert_eq!(Currencies::free_balance(LKSM, &ALICE), alice_yield);
 
-		assert_ok!(HomaLite::claim(Origin::signed(ALICE), BOB, 0));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), BOB, BatchIndex(0)));
 		assert_eq!(
 			System::events().iter().last().unwrap().event,
-			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(0, BOB, bob_yield))
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), BOB, bob_yield))
 		);
 		assert_eq!(Currencies::free_balance(LKSM, &BOB), bob_yield);
 	});
@@ -218,13 +524,13 @@ fn claim_can_handle_math_errors() {
 			staking_total: 0,
 			liquid_total: 0,
 		};
-		BatchTotalIssuanceInfo::<Runtime>::insert(0, zero_issuance);
+		BatchTotalIssuanceInfo::<Runtime>::insert(BatchIndex(0), zero_issuance);
 
 		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
 
 		// Now that the liquid currency for Batch 0 is issued, users can claim them.
 		assert_noop!(
-			HomaLite::claim(Origin::signed(ALICE), ALICE, 0),
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
 			ArithmeticError::Overflow
 		);
 	});
@@ -238,13 +544,2620 @@ fn repeated_claims_has_no_effect() {
 
 		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
 		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(10000)));
-		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, 0));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
 
 		let alice_balance = Currencies::free_balance(LKSM, &ALICE);
 
 		// The mint has already been claimed. claiming again does nothing.
-		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, 0));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
 
 		assert_eq!(Currencies::free_balance(LKSM, &ALICE), alice_balance);
 	});
 }
+
+#[test]
+fn reroll_pending_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+
+		// Cannot reroll into the same batch.
+		assert_noop!(
+			HomaLite::reroll_pending(Origin::signed(ALICE), BatchIndex(0)),
+			Error::<Runtime>::CannotRerollIntoSameBatch
+		);
+
+		// Simulate batch 0 being stuck: the current batch moves on without processing it.
+		CurrentBatch::<Runtime>::put(BatchIndex(1));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(500)));
+
+		// Users without pending amounts cannot reroll.
+		assert_noop!(
+			HomaLite::reroll_pending(Origin::signed(BOB), BatchIndex(0)),
+			Error::<Runtime>::NoPendingAmount
+		);
+
+		assert_ok!(HomaLite::reroll_pending(Origin::signed(ALICE), BatchIndex(0)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PendingRerolled(
+				BatchIndex(0),
+				BatchIndex(1),
+				ALICE,
+				dollar(1000)
+			))
+		);
+		assert_eq!(PendingAmount::<Runtime>::get(BatchIndex(0), &ALICE), 0);
+		assert_eq!(PendingAmount::<Runtime>::get(BatchIndex(1), &ALICE), dollar(1500));
+		assert_eq!(HomaLite::pending_batches(&ALICE).into_inner(), vec![BatchIndex(1)]);
+	});
+}
+
+#[test]
+fn reroll_pending_rejects_processed_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(10000)));
+
+		assert_noop!(
+			HomaLite::reroll_pending(Origin::signed(ALICE), BatchIndex(0)),
+			Error::<Runtime>::BatchAlreadyProcessed
+		);
+	});
+}
+
+#[test]
+fn request_mint_respects_mint_cooldown() {
+	ExtBuilder::default().build().execute_with(|| {
+		MintCooldownBlocks::set(10);
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_eq!(HomaLite::last_mint_block(&ALICE), Some(1));
+
+		System::set_block_number(10);
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)),
+			Error::<Runtime>::MintCooldownActive
+		);
+
+		// The cooldown is tracked per user.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1000)));
+
+		// Once the cooldown expires the user can mint again.
+		System::set_block_number(11);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_eq!(HomaLite::last_mint_block(&ALICE), Some(11));
+		assert_eq!(PendingAmount::<Runtime>::get(BatchIndex(0), &ALICE), dollar(2000));
+	});
+}
+
+#[test]
+fn request_mint_batch_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		assert_ok!(HomaLite::request_mint_batch(
+			Origin::signed(ALICE),
+			vec![(BOB, dollar(1000)), (ROOT, dollar(2000))]
+		));
+		assert_eq!(PendingAmount::<Runtime>::get(BatchIndex(0), &BOB), dollar(1000));
+		assert_eq!(PendingAmount::<Runtime>::get(BatchIndex(0), &ROOT), dollar(2000));
+		assert_eq!(PendingAmount::<Runtime>::get(BatchIndex(0), &ALICE), 0);
+		assert_eq!(
+			Currencies::free_balance(KSM, &ALICE),
+			dollar(INITIAL_BALANCE) - dollar(3000)
+		);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(3000));
+
+		let events = System::events();
+		let len = events.len();
+		assert_eq!(
+			events[len - 2].event,
+			Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(0),
+				BOB,
+				dollar(1000),
+				dollar(1000),
+				Default::default()
+			))
+		);
+		assert_eq!(
+			events[len - 1].event,
+			Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(0),
+				ROOT,
+				dollar(2000),
+				dollar(2000),
+				Default::default()
+			))
+		);
+	});
+}
+
+#[test]
+fn request_mint_batch_is_atomic_and_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		// If any request fails, the whole call is reverted.
+		assert_noop!(
+			HomaLite::request_mint_batch(
+				Origin::signed(ALICE),
+				vec![(BOB, dollar(1000)), (ROOT, dollar(INITIAL_BALANCE))]
+			),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_noop!(
+			HomaLite::request_mint_batch(
+				Origin::signed(ALICE),
+				vec![(ALICE, 1), (BOB, 1), (ROOT, 1), (RELAY_CHAIN_STASH, 1)]
+			),
+			Error::<Runtime>::TooManyMintRequests
+		);
+	});
+}
+
+#[test]
+fn batch_index_arithmetic_and_conversions_work() {
+	assert_eq!(BatchIndex::from(5), BatchIndex(5));
+	assert_eq!(u32::from(BatchIndex(5)), 5);
+	assert_eq!(BatchIndex(5).checked_add(1), Some(BatchIndex(6)));
+	assert_eq!(BatchIndex(u32::MAX).checked_add(1), None);
+	assert_eq!(BatchIndex(5).saturating_sub(2), BatchIndex(3));
+	assert_eq!(BatchIndex(1).saturating_sub(2), BatchIndex(0));
+	assert!(BatchIndex(1) < BatchIndex(2));
+}
+
+#[test]
+fn claims_never_exceed_batch_liquid_total() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		let users: Vec<AccountId> = (20u8..60).map(|i| AccountId::new([i; 32])).collect();
+		let mut staking_total = 0;
+		for (i, user) in users.iter().enumerate() {
+			// Use awkward amounts so that every claim is rounded down.
+			let amount = dollar(7) + i as Balance * 333_333_333;
+			assert_ok!(Currencies::deposit(KSM, user, amount));
+			assert_ok!(HomaLite::request_mint(Origin::signed(user.clone()), amount));
+			staking_total += amount;
+		}
+
+		let liquid_total = Currencies::total_issuance(LKSM);
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), staking_total));
+
+		for user in users.iter() {
+			assert_ok!(HomaLite::claim(Origin::signed(ALICE), user.clone(), BatchIndex(0)));
+			assert!(HomaLite::claimed_liquid(BatchIndex(0)) <= liquid_total);
+		}
+
+		let total_minted: Balance = users.iter().map(|user| Currencies::free_balance(LKSM, user)).sum();
+		assert_eq!(HomaLite::claimed_liquid(BatchIndex(0)), total_minted);
+		assert!(total_minted <= liquid_total);
+	});
+}
+
+#[test]
+fn claim_fails_when_batch_liquid_is_exhausted() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		let liquid_total = Currencies::total_issuance(LKSM);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1000)));
+
+		// The issuer records a staking total lower than the amount pending in the batch.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1500)));
+
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_noop!(
+			HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)),
+			Error::<Runtime>::BatchLiquidExhausted
+		);
+		assert!(HomaLite::claimed_liquid(BatchIndex(0)) <= liquid_total);
+	});
+}
+
+#[test]
+fn mint_bonus_is_paid_during_promo_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		// Fund the incentive account to pay for the bonus.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(ROOT),
+			INCENTIVE_ACCOUNT,
+			LKSM,
+			dollar(1000)
+		));
+
+		// Only governance can set the bonus.
+		assert_noop!(
+			HomaLite::set_mint_bonus(Origin::signed(ALICE), Ratio::saturating_from_rational(1, 10), 10),
+			BadOrigin
+		);
+		assert_ok!(HomaLite::set_mint_bonus(
+			Origin::signed(ROOT),
+			Ratio::saturating_from_rational(1, 10),
+			10
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MintBonusSet(Ratio::saturating_from_rational(1, 10), 10))
+		);
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		// liquid_to_mint = 1 * 1_000_000 / 1000 = 1000
+		let liquid_to_mint = dollar(1000);
+		let bonus = dollar(100);
+
+		// Claims inside the promo window receive the bonus.
+		System::set_block_number(10);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), liquid_to_mint + bonus);
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(
+				BatchIndex(0),
+				ALICE,
+				liquid_to_mint + bonus
+			))
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &INCENTIVE_ACCOUNT), dollar(900));
+
+		// Claims after the promo window do not.
+		System::set_block_number(11);
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), liquid_to_mint);
+		assert_eq!(Currencies::free_balance(LKSM, &INCENTIVE_ACCOUNT), dollar(900));
+	});
+}
+
+#[test]
+fn mint_bonus_requires_funded_incentive_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_mint_bonus(
+			Origin::signed(ROOT),
+			Ratio::saturating_from_rational(1, 10),
+			10
+		));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Funds held by the pallet account are not used for bonuses.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(ROOT),
+			HomaLite::account_id(),
+			LKSM,
+			dollar(1000)
+		));
+		assert_noop!(
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::InsufficientIncentiveFunds
+		);
+
+		assert_ok!(Currencies::transfer(
+			Origin::signed(ROOT),
+			INCENTIVE_ACCOUNT,
+			LKSM,
+			dollar(99)
+		));
+		assert_noop!(
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::InsufficientIncentiveFunds
+		);
+
+		assert_ok!(Currencies::transfer(
+			Origin::signed(ROOT),
+			INCENTIVE_ACCOUNT,
+			LKSM,
+			dollar(1)
+		));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1100));
+		assert_eq!(Currencies::free_balance(LKSM, &INCENTIVE_ACCOUNT), 0);
+	});
+}
+
+#[test]
+fn no_funds_are_lost_across_mint_issue_claim_cycle() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Setup the relay chain's stash account.
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		let ksm_issuance = Currencies::total_issuance(KSM);
+		let lksm_issuance = Currencies::total_issuance(LKSM);
+		let requests = vec![(ALICE, dollar(1234)), (BOB, 5_678_912_345_678), (ALICE, 3)];
+		let total_requested: Balance = requests.iter().map(|(_, amount)| amount).sum();
+
+		for (who, amount) in requests.iter() {
+			assert_ok!(HomaLite::request_mint(Origin::signed(who.clone()), *amount));
+		}
+
+		// All staking currency locked is moved into the stash, and none is created or destroyed.
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), total_requested);
+		assert_eq!(
+			Currencies::free_balance(KSM, &ALICE) + Currencies::free_balance(KSM, &BOB),
+			dollar(INITIAL_BALANCE) * 2 - total_requested
+		);
+		assert_eq!(Currencies::total_issuance(KSM), ksm_issuance);
+
+		let staking_total = dollar(7777);
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), staking_total));
+
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+
+		// Each claim is rounded down, so the minted amount matches the exact rate to within 1 unit
+		// per claim.
+		let rate = Ratio::checked_from_rational(lksm_issuance, staking_total).unwrap();
+		let alice_expected = rate.checked_mul_int(dollar(1234) + 3).unwrap();
+		let bob_expected = rate.checked_mul_int(5_678_912_345_678u128).unwrap();
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), alice_expected);
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), bob_expected);
+
+		let total_minted = alice_expected + bob_expected;
+		let exact_total = total_requested * lksm_issuance / staking_total;
+		assert!(total_minted <= exact_total);
+		assert!(exact_total - total_minted <= 2);
+
+		// Liquid currency is only created by claims.
+		assert_eq!(Currencies::total_issuance(LKSM), lksm_issuance + total_minted);
+		assert_eq!(HomaLite::claimed_liquid(BatchIndex(0)), total_minted);
+		assert_eq!(PendingAmount::<Runtime>::iter_prefix(BatchIndex(0)).count(), 0);
+		// Claiming does not touch the staking currency in the stash.
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), total_requested);
+	});
+}
+
+#[test]
+fn claim_respects_permissionless_claim_flag() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert!(HomaLite::permissionless_claim());
+
+		assert_noop!(
+			HomaLite::set_permissionless_claim(Origin::signed(ALICE), false),
+			BadOrigin
+		);
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1000)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(2000)));
+
+		// Only the owner, or Governance, can claim when claims are owner-only.
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), false));
+		assert!(!HomaLite::permissionless_claim());
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PermissionlessClaimSet(false))
+		);
+
+		assert_noop!(
+			HomaLite::claim(Origin::signed(BOB), ALICE, BatchIndex(0)),
+			Error::<Runtime>::ClaimNotPermitted
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::claim(Origin::signed(ROOT), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(500_000));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(500_000));
+	});
+
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Anyone can claim on behalf of a user when claims are permissionless.
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), true));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1_000_000));
+	});
+}
+
+#[test]
+fn pending_mints_are_paged_in_ascending_batch_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		for amount in [dollar(100), dollar(200), dollar(300)].iter() {
+			assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), *amount));
+			assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		}
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(1)));
+
+		assert_eq!(
+			HomaLite::pending_mints(&ALICE),
+			vec![(BatchIndex(0), dollar(100)), (BatchIndex(2), dollar(300))]
+		);
+		assert_eq!(HomaLite::pending_mints(&BOB), vec![]);
+
+		assert_eq!(
+			HomaLite::pending_mints_paged(&ALICE, BatchIndex(0), 1),
+			vec![(BatchIndex(0), dollar(100))]
+		);
+		assert_eq!(
+			HomaLite::pending_mints_paged(&ALICE, BatchIndex(1), 1),
+			vec![(BatchIndex(2), dollar(300))]
+		);
+		assert_eq!(HomaLite::pending_mints_paged(&ALICE, BatchIndex(3), 1), vec![]);
+		assert_eq!(HomaLite::pending_mints_paged(&ALICE, BatchIndex(0), 0), vec![]);
+	});
+}
+
+#[test]
+fn force_refund_batch_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(2000)));
+
+		assert_noop!(
+			HomaLite::force_refund_batch(Origin::signed(ALICE), BatchIndex(0), 3),
+			BadOrigin
+		);
+
+		// Refunds are chunked by `max`.
+		assert_ok!(HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(0), 1));
+		let remaining: Vec<(AccountId, Balance)> = PendingAmount::<Runtime>::iter_prefix(BatchIndex(0)).collect();
+		assert_eq!(remaining.len(), 1);
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BatchForceRefunded(
+				BatchIndex(0),
+				1,
+				dollar(3000) - remaining[0].1
+			))
+		);
+
+		assert_ok!(HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(0), 5));
+		assert_eq!(PendingAmount::<Runtime>::iter_prefix(BatchIndex(0)).count(), 0);
+		assert_eq!(HomaLite::pending_batches(&ALICE).len(), 0);
+		assert_eq!(HomaLite::pending_batches(&BOB).len(), 0);
+		assert_eq!(Currencies::free_balance(KSM, &ALICE), dollar(INITIAL_BALANCE));
+		assert_eq!(Currencies::free_balance(KSM, &BOB), dollar(INITIAL_BALANCE));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), 0);
+
+		// Processed batches cannot be refunded.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_noop!(
+			HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(0), 1),
+			Error::<Runtime>::BatchAlreadyProcessed
+		);
+	});
+}
+
+#[test]
+fn simulate_claim_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+
+		// liquid_total = 1_000_000 LKSM, so the rate is 500 LKSM per KSM.
+		assert_eq!(
+			HomaLite::simulate_claim(&ALICE, BatchIndex(0), dollar(2000)),
+			dollar(500_000)
+		);
+		assert_eq!(HomaLite::simulate_claim(&BOB, BatchIndex(0), dollar(2000)), 0);
+		assert_eq!(HomaLite::simulate_claim(&ALICE, BatchIndex(0), 0), 0);
+		assert_eq!(HomaLite::batch_total_issuance_info(BatchIndex(0)), None);
+
+		// Active mint bonuses are included.
+		assert_ok!(HomaLite::set_mint_bonus(
+			Origin::signed(ROOT),
+			Ratio::saturating_from_rational(1, 10),
+			100
+		));
+		assert_eq!(
+			HomaLite::simulate_claim(&ALICE, BatchIndex(0), dollar(2000)),
+			dollar(550_000)
+		);
+
+		// The simulation matches the actual claim.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(ROOT),
+			INCENTIVE_ACCOUNT,
+			LKSM,
+			dollar(50_000)
+		));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(2000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(550_000));
+	});
+}
+
+#[test]
+fn batch_handler_is_called_when_batch_is_processed() {
+	ExtBuilder::default().build().execute_with(|| {
+		ProcessedBatches::set(vec![]);
+
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(4000)));
+		assert_noop!(
+			HomaLite::issue(Origin::signed(ROOT), 0),
+			Error::<Runtime>::InvalidStakedCurrencyTotalIssuance
+		);
+
+		// rate = liquid_total / staking_total
+		assert_eq!(
+			ProcessedBatches::get(),
+			vec![
+				(BatchIndex(0), Ratio::saturating_from_integer(1000)),
+				(BatchIndex(1), Ratio::saturating_from_integer(250))
+			]
+		);
+	});
+}
+
+#[test]
+fn adjust_pending_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+
+		assert_noop!(
+			HomaLite::adjust_pending(Origin::signed(ALICE), ALICE, BatchIndex(0), dollar(500)),
+			BadOrigin
+		);
+
+		// Decreasing the pending amount refunds the difference from the stash.
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			ALICE,
+			BatchIndex(0),
+			dollar(400)
+		));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), dollar(400));
+		assert_eq!(
+			Currencies::free_balance(KSM, &ALICE),
+			dollar(INITIAL_BALANCE) - dollar(400)
+		);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(400));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PendingAdjusted(
+				BatchIndex(0),
+				ALICE,
+				dollar(1000),
+				dollar(400)
+			))
+		);
+
+		// Increasing the pending amount transfers the difference to the stash.
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			BOB,
+			BatchIndex(0),
+			dollar(300)
+		));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &BOB), dollar(300));
+		assert_eq!(HomaLite::pending_batches(&BOB).into_inner(), vec![BatchIndex(0)]);
+		assert_eq!(
+			Currencies::free_balance(KSM, &BOB),
+			dollar(INITIAL_BALANCE) - dollar(300)
+		);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(700));
+
+		// Setting the pending amount to zero removes it.
+		assert_ok!(HomaLite::adjust_pending(Origin::signed(ROOT), BOB, BatchIndex(0), 0));
+		assert!(!PendingAmount::<Runtime>::contains_key(BatchIndex(0), &BOB));
+		assert_eq!(HomaLite::pending_batches(&BOB).len(), 0);
+		assert_eq!(Currencies::free_balance(KSM, &BOB), dollar(INITIAL_BALANCE));
+
+		// Future batches cannot be adjusted.
+		assert_noop!(
+			HomaLite::adjust_pending(Origin::signed(ROOT), ALICE, BatchIndex(1), dollar(500)),
+			Error::<Runtime>::BatchNotOpen
+		);
+
+		// Batches split off the current batch can be adjusted.
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			ALICE,
+			BatchIndex(0),
+			dollar(500)
+		));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), dollar(500));
+
+		// Processed batches cannot be adjusted.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_noop!(
+			HomaLite::adjust_pending(Origin::signed(ROOT), ALICE, BatchIndex(0), dollar(500)),
+			Error::<Runtime>::BatchAlreadyProcessed
+		);
+
+		// Batches skipped without being processed cannot be adjusted either.
+		CurrentBatch::<Runtime>::put(BatchIndex(5));
+		assert_noop!(
+			HomaLite::adjust_pending(Origin::signed(ROOT), ALICE, BatchIndex(3), dollar(500)),
+			Error::<Runtime>::BatchNotOpen
+		);
+	});
+}
+
+#[test]
+fn reconcile_stash_reports_mismatch() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(HomaLite::reconcile(), Error::<Runtime>::RelayChainStashAccountNotSet);
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(500)));
+		assert_eq!(HomaLite::reconcile(), Ok((dollar(1500), dollar(1500))));
+
+		assert_noop!(HomaLite::reconcile_stash(Origin::signed(ALICE)), BadOrigin);
+
+		// No mismatch while the accounting is in sync.
+		System::reset_events();
+		assert_ok!(HomaLite::reconcile_stash(Origin::signed(ROOT)));
+		assert_eq!(System::events(), vec![]);
+
+		// Shortfalls within the tolerance are not reported.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(RELAY_CHAIN_STASH),
+			ALICE,
+			KSM,
+			ReconciliationTolerance::get()
+		));
+		System::reset_events();
+		assert_ok!(HomaLite::reconcile_stash(Origin::signed(ROOT)));
+		assert_eq!(System::events(), vec![]);
+
+		// Desync the stash from the accounting.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(RELAY_CHAIN_STASH),
+			ALICE,
+			KSM,
+			dollar(1)
+		));
+		let actual = dollar(1499) - ReconciliationTolerance::get();
+		assert_eq!(HomaLite::reconcile(), Ok((dollar(1500), actual)));
+		assert_ok!(HomaLite::reconcile_stash(Origin::signed(ROOT)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::ReconciliationMismatch(dollar(1500), actual))
+		);
+
+		// Processed batches are no longer expected in the stash.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::reconcile(), Ok((0, actual)));
+	});
+}
+
+#[test]
+fn reconcile_stash_ignores_restaked_pending_amounts() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_auto_compound(Origin::signed(BOB), true));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(2)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		// The processed batch is staked on the relay chain.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(RELAY_CHAIN_STASH),
+			ROOT,
+			KSM,
+			dollar(3)
+		));
+
+		// Restakes into the current batch, and into a batch split off it, mixed with deposits.
+		assert_ok!(HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(4)));
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(5)));
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(1)), dollar(5));
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(2)), dollar(6));
+		assert_eq!(HomaLite::reconcile(), Ok((dollar(9), dollar(9))));
+
+		System::reset_events();
+		assert_ok!(HomaLite::reconcile_stash(Origin::signed(ROOT)));
+		assert_eq!(System::events(), vec![]);
+
+		// A real shortfall is still reported, against the deposits only.
+		assert_ok!(Currencies::transfer(
+			Origin::signed(RELAY_CHAIN_STASH),
+			ROOT,
+			KSM,
+			dollar(1)
+		));
+		assert_ok!(HomaLite::reconcile_stash(Origin::signed(ROOT)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::ReconciliationMismatch(dollar(9), dollar(8)))
+		);
+	});
+}
+
+#[test]
+fn request_mint_with_memo_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		let memo: BoundedVec<u8, MaxMemoLen> = b"exchange-ref-0001".to_vec().try_into().unwrap();
+		assert_ok!(HomaLite::request_mint_with_memo(
+			Origin::signed(ALICE),
+			dollar(1000),
+			memo.clone()
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(0),
+				ALICE,
+				dollar(1000),
+				dollar(1000),
+				memo
+			))
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), dollar(1000));
+
+		// The memo is bounded by MaxMemoLen.
+		assert_ok!(HomaLite::request_mint_with_memo(
+			Origin::signed(BOB),
+			dollar(1000),
+			vec![0u8; 32].try_into().unwrap()
+		));
+		assert!(BoundedVec::<u8, MaxMemoLen>::try_from(vec![0u8; 33]).is_err());
+
+		// Mints with a memo are processed right away when `InstantBatch` is on.
+		assert_ok!(HomaLite::set_instant_batch(Origin::signed(ROOT), true));
+		assert_ok!(HomaLite::request_mint_with_memo(Origin::signed(ALICE), dollar(1), memo));
+		assert_eq!(HomaLite::current_batch(), BatchIndex(1));
+		assert!(HomaLite::batch_total_issuance_info(BatchIndex(0)).is_some());
+	});
+}
+
+#[test]
+fn abnormal_rate_deviation_auto_pauses() {
+	ExtBuilder::default().build().execute_with(|| {
+		AutoPauseThreshold::set(Ratio::saturating_from_rational(1, 10));
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+
+		// Deviations within the threshold do not pause.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1050)));
+		assert!(!HomaLite::is_paused());
+
+		// Deviations beyond the threshold pause the module.
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(2000)));
+		assert!(HomaLite::is_paused());
+		let old_rate = Ratio::checked_from_rational(dollar(1_000_000), dollar(1050)).unwrap();
+		let new_rate = Ratio::checked_from_rational(dollar(1_000_000), dollar(2000)).unwrap();
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::AutoPaused(old_rate, new_rate))
+		);
+
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(BOB), dollar(1000)),
+			Error::<Runtime>::ModulePaused
+		);
+		assert_noop!(
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::ModulePaused
+		);
+		assert_noop!(
+			HomaLite::issue(Origin::signed(ROOT), dollar(2000)),
+			Error::<Runtime>::ModulePaused
+		);
+
+		// Only Governance can unpause.
+		assert_noop!(HomaLite::set_paused(Origin::signed(ALICE), false), BadOrigin);
+		assert_ok!(HomaLite::set_paused(Origin::signed(ROOT), false));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PausedSet(false))
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1_000_000));
+	});
+}
+
+#[test]
+fn total_pending_staking_is_tracked() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_eq!(HomaLite::total_pending_staking(), 0);
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(500)));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(1500));
+
+		// Processing a batch does not change the total until it is claimed.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(3000)));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(1500));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(500));
+
+		// Cancelled and corrected amounts are accounted for.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(300)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(200)));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(1000));
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			ALICE,
+			BatchIndex(1),
+			dollar(100)
+		));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(800));
+		assert_ok!(HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(1), 2));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(500));
+	});
+}
+
+#[test]
+fn split_current_batch_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		ProcessedBatches::set(vec![]);
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+
+		// Only governance can split the batch.
+		assert_noop!(HomaLite::split_current_batch(Origin::signed(ALICE)), BadOrigin);
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BatchSplit(BatchIndex(0), BatchIndex(1)))
+		);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(1));
+
+		// New mints go into the new batch, and the old batch's pending amounts are kept.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(500)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), dollar(1000));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &BOB), dollar(500));
+		assert_eq!(HomaLite::reconcile(), Ok((dollar(1500), dollar(1500))));
+
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_noop!(
+			HomaLite::split_current_batch(Origin::signed(ROOT)),
+			Error::<Runtime>::TooManyBatchSplits
+		);
+		assert_eq!(
+			HomaLite::split_batches().into_inner(),
+			vec![BatchIndex(0), BatchIndex(1)]
+		);
+
+		// Split batches are processed together with the current batch.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1_000_000)));
+		let rate = Ratio::saturating_from_rational(1, 1);
+		assert_eq!(
+			ProcessedBatches::get(),
+			vec![(BatchIndex(0), rate), (BatchIndex(1), rate), (BatchIndex(2), rate)]
+		);
+		assert_eq!(HomaLite::split_batches().into_inner(), vec![]);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(3));
+
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(1)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(500));
+	});
+}
+
+#[test]
+fn staking_to_liquid_rounds_down() {
+	let rate = |liquid_total, staking_total| {
+		TotalIssuanceInfo {
+			staking_total,
+			liquid_total,
+		}
+		.exchange_rate()
+		.unwrap()
+	};
+	assert_eq!(
+		staking_to_liquid(dollar(1), rate(dollar(1_000_000), dollar(1000))),
+		Some(dollar(1000))
+	);
+	assert_eq!(staking_to_liquid(1, rate(2, 3)), Some(0));
+	assert_eq!(staking_to_liquid(2, rate(2, 3)), Some(1));
+	// 2/3 is not exactly representable, so even the full staking total rounds down.
+	assert_eq!(staking_to_liquid(3, rate(2, 3)), Some(1));
+	assert_eq!(
+		staking_to_liquid(Balance::max_value(), Ratio::saturating_from_integer(2)),
+		None
+	);
+	assert_eq!(staking_to_liquid(1, Ratio::zero()), Some(0));
+	assert_eq!(
+		TotalIssuanceInfo {
+			staking_total: 0,
+			liquid_total: 2
+		}
+		.exchange_rate(),
+		None
+	);
+}
+
+#[test]
+fn liquid_to_staking_rounds_down() {
+	assert_eq!(
+		liquid_to_staking(dollar(1000), Ratio::saturating_from_integer(1000)),
+		Some(dollar(1))
+	);
+	assert_eq!(liquid_to_staking(1, Ratio::saturating_from_integer(2)), Some(0));
+	assert_eq!(liquid_to_staking(3, Ratio::saturating_from_integer(2)), Some(1));
+	assert_eq!(liquid_to_staking(2, Ratio::saturating_from_rational(2, 3)), Some(3));
+	// Amounts too large for an intermediate fixed point number are still converted.
+	assert_eq!(
+		liquid_to_staking(Balance::max_value(), Ratio::saturating_from_integer(2)),
+		Some(Balance::max_value() / 2)
+	);
+	assert_eq!(
+		liquid_to_staking(Balance::max_value(), Ratio::saturating_from_rational(1, 2)),
+		None
+	);
+	assert_eq!(liquid_to_staking(1, Ratio::zero()), None);
+
+	// Converting back never returns more than the original amount, even when the rate is not
+	// exactly representable.
+	let rate = Ratio::saturating_from_rational(2, 3);
+	for staked in vec![1, 2, 3, 10, dollar(1), dollar(7) + 1] {
+		let liquid = staking_to_liquid(staked, rate).unwrap();
+		assert!(liquid_to_staking(liquid, rate).unwrap() <= staked);
+	}
+}
+
+#[test]
+fn claimable_batches_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		// Batch 0: claimed by everyone.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		// Batch 1: no mints.
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		// Batches 2 and 3: unclaimed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		// Batch 4: not yet processed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+
+		assert_eq!(
+			HomaLite::claimable_batches(BatchIndex(0), 10),
+			vec![BatchIndex(2), BatchIndex(3)]
+		);
+		assert_eq!(HomaLite::claimable_batches(BatchIndex(0), 0), vec![]);
+		// Only `limit` batches are read, whether they are claimable or not.
+		assert_eq!(HomaLite::claimable_batches(BatchIndex(0), 2), vec![]);
+		assert_eq!(HomaLite::claimable_batches(BatchIndex(0), 3), vec![BatchIndex(2)]);
+		assert_eq!(HomaLite::claimable_batches(BatchIndex(3), 10), vec![BatchIndex(3)]);
+		assert_eq!(HomaLite::claimable_batches(BatchIndex(4), 10), vec![]);
+
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(2)));
+		assert_eq!(HomaLite::claimable_batches(BatchIndex(0), 10), vec![BatchIndex(3)]);
+	});
+}
+
+#[test]
+fn total_claimable_sums_processed_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_eq!(HomaLite::total_claimable(&ALICE), 0);
+
+		// Batch 0 is processed at a rate of 1000, batch 1 at 500.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(2)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(2000)));
+		// Batch 2 is not yet processed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(5)));
+
+		assert_eq!(HomaLite::total_claimable(&ALICE), dollar(2000));
+		assert_eq!(HomaLite::total_claimable(&BOB), 0);
+
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(1)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(2000));
+		assert_eq!(HomaLite::total_claimable(&ALICE), 0);
+	});
+}
+
+#[test]
+fn claims_are_routed_through_mint_router() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::mint_router(), None);
+
+		// By default Liquid currency is minted directly to the user.
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+
+		// Only governance can set the router.
+		assert_noop!(
+			HomaLite::set_mint_router(Origin::signed(ALICE), Some(RELAY_CHAIN_STASH)),
+			BadOrigin
+		);
+		assert_ok!(HomaLite::set_mint_router(Origin::signed(ROOT), Some(RELAY_CHAIN_STASH)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MintRouterSet(Some(RELAY_CHAIN_STASH)))
+		);
+
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), 0);
+		assert_eq!(Currencies::free_balance(LKSM, &RELAY_CHAIN_STASH), dollar(1000));
+		let events = System::events();
+		let len = events.len();
+		assert_eq!(
+			events[len - 2].event,
+			Event::HomaLite(crate::Event::MintRouted(
+				BatchIndex(0),
+				BOB,
+				RELAY_CHAIN_STASH,
+				dollar(1000)
+			))
+		);
+		assert_eq!(
+			events[len - 1].event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), BOB, dollar(1000)))
+		);
+
+		assert_ok!(HomaLite::set_mint_router(Origin::signed(ROOT), None));
+		assert_eq!(HomaLite::mint_router(), None);
+	});
+}
+
+#[test]
+fn mint_cooldown_spanning_eras_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		MintCooldownBlocks::set(BLOCKS_PER_ERA);
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_eq!(MockClock::current_era(), 0);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+
+		// The cooldown started mid-era, so it has not expired at the start of the next era.
+		MockClock::advance_eras(1);
+		assert_eq!(MockClock::current_era(), 1);
+		assert_eq!(System::block_number(), BLOCKS_PER_ERA);
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)),
+			Error::<Runtime>::MintCooldownActive
+		);
+
+		MockClock::advance_eras(1);
+		assert_eq!(MockClock::current_era(), 2);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+	});
+}
+
+#[test]
+fn governance_burn_liquid_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let issuance = Currencies::total_issuance(LKSM);
+
+		// Only governance can burn.
+		assert_noop!(
+			HomaLite::governance_burn_liquid(Origin::signed(ALICE), ROOT, dollar(1000)),
+			BadOrigin
+		);
+		assert_noop!(
+			HomaLite::governance_burn_liquid(Origin::signed(ROOT), ALICE, dollar(1000)),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(HomaLite::governance_burn_liquid(
+			Origin::signed(ROOT),
+			ROOT,
+			dollar(1000)
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidBurnedByGovernance(ROOT, dollar(1000)))
+		);
+		assert_eq!(Currencies::total_issuance(LKSM), issuance - dollar(1000));
+		assert_eq!(
+			Currencies::free_balance(LKSM, &ROOT),
+			dollar(INITIAL_BALANCE) - dollar(1000)
+		);
+	});
+}
+
+#[test]
+fn protocol_status_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			HomaLite::protocol_status(),
+			ProtocolStatus {
+				current_batch: BatchIndex(0),
+				exchange_rate: None,
+				total_pending_staking: 0,
+				liquid_total_issuance: dollar(INITIAL_BALANCE),
+				paused: false,
+			}
+		);
+
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(2)));
+		assert_ok!(HomaLite::set_paused(Origin::signed(ROOT), true));
+
+		assert_eq!(
+			HomaLite::protocol_status(),
+			ProtocolStatus {
+				current_batch: BatchIndex(1),
+				exchange_rate: Some(Ratio::saturating_from_integer(1000)),
+				total_pending_staking: dollar(3),
+				liquid_total_issuance: dollar(INITIAL_BALANCE),
+				paused: true,
+			}
+		);
+	});
+}
+
+#[test]
+fn claim_and_restake_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_noop!(
+			HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::LiquidCurrencyNotIssuedForThisBatch
+		);
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let lksm_issuance = Currencies::total_issuance(LKSM);
+
+		assert_noop!(
+			HomaLite::claim_and_restake(Origin::signed(BOB), BOB, BatchIndex(0)),
+			Error::<Runtime>::NoPendingAmount
+		);
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), false));
+		assert_noop!(
+			HomaLite::claim_and_restake(Origin::signed(BOB), ALICE, BatchIndex(0)),
+			Error::<Runtime>::ClaimNotPermitted
+		);
+
+		assert_ok!(HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, dollar(1000)))));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(1),
+				ALICE,
+				dollar(1),
+				dollar(1),
+				Default::default()
+			))
+		);
+
+		// The claimed Liquid currency is restaked, so nothing is minted and no Staking currency
+		// moves.
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), 0);
+		assert_eq!(Currencies::total_issuance(LKSM), lksm_issuance);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(1));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), 0);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &ALICE), dollar(1));
+		assert_eq!(HomaLite::pending_batches(&ALICE).into_inner(), vec![BatchIndex(1)]);
+		assert_eq!(HomaLite::total_pending_staking(), dollar(1));
+	});
+}
+
+#[test]
+fn restaked_pending_amounts_are_not_refunded_from_the_stash() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(2)));
+
+		// Only Bob's deposit is in the stash for batch 1. Alice's restake is already staked.
+		assert_eq!(HomaLite::restaked_pending(BatchIndex(1), &ALICE), dollar(1));
+		assert_eq!(HomaLite::batch_restaked_total(BatchIndex(1)), dollar(1));
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(1)), dollar(3));
+		assert_eq!(HomaLite::staking_in_transit(), dollar(2));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(3));
+		assert_eq!(HomaLite::reconcile(), Ok((dollar(2), dollar(3))));
+		assert_ok!(HomaLite::reconcile_stash(Origin::signed(ROOT)));
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, Event::HomaLite(crate::Event::ReconciliationMismatch(..)))));
+
+		// Bob is refunded from the stash, and Alice's restake is minted back as Liquid currency.
+		let lksm_issuance = Currencies::total_issuance(LKSM);
+		assert_ok!(HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(1), 10));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BatchForceRefunded(BatchIndex(1), 2, dollar(3)))
+		);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(1));
+		assert_eq!(Currencies::free_balance(KSM, &BOB), dollar(INITIAL_BALANCE));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(Currencies::total_issuance(LKSM), lksm_issuance + dollar(1000));
+		assert_eq!(HomaLite::restaked_pending(BatchIndex(1), &ALICE), 0);
+		assert_eq!(HomaLite::batch_restaked_total(BatchIndex(1)), 0);
+		assert_eq!(HomaLite::reconcile(), Ok((0, dollar(1))));
+	});
+}
+
+#[test]
+fn adjusting_restaked_pending_amounts_only_refunds_deposits_from_the_stash() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(2)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &ALICE), dollar(3));
+
+		// The deposited part is refunded from the stash first.
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			ALICE,
+			BatchIndex(1),
+			dollar(2)
+		));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(2));
+		assert_eq!(HomaLite::restaked_pending(BatchIndex(1), &ALICE), dollar(1));
+
+		// The rest of the decrease comes out of the restaked part, as Liquid currency.
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			ALICE,
+			BatchIndex(1),
+			dollar(1) / 2
+		));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(1));
+		assert_eq!(
+			Currencies::free_balance(KSM, &ALICE),
+			dollar(INITIAL_BALANCE) - dollar(1)
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(500));
+		assert_eq!(HomaLite::restaked_pending(BatchIndex(1), &ALICE), dollar(1) / 2);
+		assert_eq!(HomaLite::batch_restaked_total(BatchIndex(1)), dollar(1) / 2);
+		assert_eq!(HomaLite::reconcile(), Ok((0, dollar(1))));
+	});
+}
+
+#[test]
+fn large_restakes_are_held_for_confirmation() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::set_large_mint_threshold(
+			Origin::signed(ROOT),
+			Some(dollar(1) / 2)
+		));
+
+		// Restaking cannot bypass the large mint threshold.
+		assert_ok!(HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::claim_and_restake(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LargeMintPending(BatchIndex(1), BOB, dollar(1)))
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &ALICE), 0);
+		assert_eq!(HomaLite::unconfirmed_mints(BatchIndex(1), &ALICE), dollar(1));
+		assert_eq!(HomaLite::unconfirmed_restaked(BatchIndex(1), &ALICE), dollar(1));
+		assert_eq!(HomaLite::total_unconfirmed_restaked(), dollar(2));
+		assert_eq!(HomaLite::staking_in_transit(), 0);
+
+		// Confirming moves the restaked part with the mint.
+		assert_ok!(HomaLite::confirm_large_mint(Origin::signed(ROOT), ALICE, BatchIndex(1)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &ALICE), dollar(1));
+		assert_eq!(HomaLite::restaked_pending(BatchIndex(1), &ALICE), dollar(1));
+		assert_eq!(HomaLite::unconfirmed_restaked(BatchIndex(1), &ALICE), 0);
+
+		// Rejecting mints the restaked part back as Liquid currency, leaving the stash alone.
+		assert_ok!(HomaLite::reject_large_mint(Origin::signed(ROOT), BOB, BatchIndex(1)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(1000));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(2));
+		assert_eq!(HomaLite::total_unconfirmed_restaked(), 0);
+		assert_eq!(HomaLite::reconcile(), Ok((0, dollar(2))));
+	});
+}
+
+#[test]
+fn claim_respects_liquid_currency_issuance_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let lksm_issuance = Currencies::total_issuance(LKSM);
+
+		// Only governance can set the cap.
+		assert_noop!(
+			HomaLite::set_liquid_currency_issuance_cap(Origin::signed(ALICE), Some(lksm_issuance)),
+			BadOrigin
+		);
+		assert_ok!(HomaLite::set_liquid_currency_issuance_cap(
+			Origin::signed(ROOT),
+			Some(lksm_issuance + dollar(1999))
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyIssuanceCapSet(Some(
+				lksm_issuance + dollar(1999)
+			)))
+		);
+
+		// Minting up to the cap is allowed.
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::total_issuance(LKSM), lksm_issuance + dollar(1000));
+
+		// Minting above the cap is not.
+		assert_noop!(
+			HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)),
+			Error::<Runtime>::ExceededLiquidIssuanceCap
+		);
+		assert_ok!(HomaLite::set_liquid_currency_issuance_cap(
+			Origin::signed(ROOT),
+			Some(lksm_issuance + dollar(2000))
+		));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::total_issuance(LKSM), lksm_issuance + dollar(2000));
+	});
+}
+
+#[test]
+fn auto_claims_are_processed_over_multiple_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		let users: Vec<AccountId> = (100u8..110).map(|i| AccountId::new([i; 32])).collect();
+		for user in users.iter() {
+			assert_ok!(Currencies::transfer(
+				Origin::signed(ALICE),
+				user.clone(),
+				KSM,
+				dollar(1)
+			));
+			assert_ok!(HomaLite::request_mint(Origin::signed(user.clone()), dollar(1)));
+		}
+
+		// Nothing is claimed before the batch is processed.
+		HomaLite::on_initialize(2);
+		assert_eq!(HomaLite::auto_claim_cursor(), None);
+		assert_eq!(PendingAmount::<Runtime>::iter_prefix(BatchIndex(0)).count(), 10);
+
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::auto_claim_cursor(), Some((BatchIndex(0), None)));
+
+		// At most `MaxAutoClaimsPerBlock` are claimed in each block.
+		let mut remaining = 10;
+		let mut blocks = 0;
+		while HomaLite::auto_claim_cursor().is_some() {
+			HomaLite::on_initialize(3 + blocks);
+			blocks += 1;
+			let left = PendingAmount::<Runtime>::iter_prefix(BatchIndex(0)).count();
+			assert!(remaining - left <= MaxAutoClaimsPerBlock::get() as usize);
+			remaining = left;
+		}
+		assert_eq!(remaining, 0);
+		assert_eq!(blocks, 4);
+		for user in users.iter() {
+			assert_eq!(Currencies::free_balance(LKSM, user), dollar(1000));
+		}
+		assert_eq!(HomaLite::total_pending_staking(), 0);
+	});
+}
+
+#[test]
+fn auto_claims_wait_while_claims_are_owner_only() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), false));
+
+		HomaLite::on_initialize(2);
+		assert_eq!(HomaLite::auto_claim_cursor(), Some((BatchIndex(0), None)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), dollar(1));
+
+		// Auto claims resume once anyone can claim again.
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), true));
+		HomaLite::on_initialize(3);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), 0);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+	});
+}
+
+#[test]
+fn realized_yield_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_eq!(HomaLite::realized_yield(&ALICE, BatchIndex(0)), None);
+
+		// Batch 0: 1 Staking currency is worth 1000 Liquid currency.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			HomaLite::batch_exchange_rate(BatchIndex(0)),
+			Some(Ratio::saturating_from_integer(1000))
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(
+			HomaLite::realized_yield(&ALICE, BatchIndex(0)),
+			Some(Ratio::saturating_from_integer(1))
+		);
+
+		// Batch 1: staking rewards grew the staking total by 25%, to 1 : 800.
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(125_125) / 100));
+		assert_eq!(
+			HomaLite::batch_exchange_rate(BatchIndex(1)),
+			Some(Ratio::saturating_from_integer(800))
+		);
+		assert_eq!(
+			HomaLite::realized_yield(&ALICE, BatchIndex(0)),
+			Some(Ratio::saturating_from_rational(125, 100))
+		);
+
+		// Only the user's claims from `since_batch` onwards count.
+		assert_eq!(HomaLite::realized_yield(&ALICE, BatchIndex(1)), None);
+		assert_eq!(HomaLite::realized_yield(&BOB, BatchIndex(0)), None);
+
+		// Unprocessed batches have no rate.
+		assert_eq!(HomaLite::realized_yield(&ALICE, BatchIndex(2)), None);
+	});
+}
+
+#[test]
+fn request_mint_pooled_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		let depositor = AccountId::new([100u8; 32]);
+
+		assert_ok!(HomaLite::request_mint_pooled(
+			Origin::signed(ALICE),
+			vec![(BOB, dollar(1)), (depositor.clone(), dollar(2))]
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PooledMintRequested(BatchIndex(0), ALICE, 2, dollar(3)))
+		);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), dollar(4));
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(0), ALICE), &BOB), dollar(1));
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(4));
+
+		// A pool can only mint for a bounded number of depositors in each batch.
+		assert_noop!(
+			HomaLite::request_mint_pooled(Origin::signed(ALICE), vec![(ROOT, dollar(1)), (ROOT, dollar(1))]),
+			Error::<Runtime>::TooManyMintRequests
+		);
+
+		// Each depositor receives the Liquid currency for their share, and the pool the rest.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(1000));
+		assert_eq!(Currencies::free_balance(LKSM, &depositor), dollar(2000));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::PooledLiquidClaimed(
+				BatchIndex(0),
+				ALICE,
+				depositor.clone(),
+				dollar(2000)
+			))));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, dollar(4000)))
+		);
+		assert_eq!(PooledShares::<Runtime>::iter_prefix((BatchIndex(0), ALICE)).count(), 0);
+	});
+}
+
+#[test]
+fn pooled_shares_follow_the_pools_pending_amount() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		let depositor = AccountId::new([100u8; 32]);
+		assert_ok!(HomaLite::request_mint_pooled(
+			Origin::signed(ALICE),
+			vec![(BOB, dollar(1)), (depositor.clone(), dollar(2))]
+		));
+
+		// Adjusting the pool's pending amount scales its depositors' shares.
+		assert_ok!(HomaLite::adjust_pending(
+			Origin::signed(ROOT),
+			ALICE,
+			BatchIndex(0),
+			dollar(6)
+		));
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(0), ALICE), &BOB), dollar(2));
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(0), ALICE), &depositor), dollar(4));
+
+		// Rerolling the pool's pending amount moves its depositors' shares.
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::reroll_pending(Origin::signed(ALICE), BatchIndex(0)));
+		assert_eq!(PooledShares::<Runtime>::iter_prefix((BatchIndex(0), ALICE)).count(), 0);
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(1), ALICE), &BOB), dollar(2));
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(1), ALICE), &depositor), dollar(4));
+
+		// Pooled claims cannot be restaked, but can be claimed.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_noop!(
+			HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(1)),
+			Error::<Runtime>::CannotRestakePooledClaim
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(1)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(2000));
+		assert_eq!(Currencies::free_balance(LKSM, &depositor), dollar(4000));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), 0);
+
+		// Reducing the pending amount to zero removes the shares.
+		assert_ok!(HomaLite::request_mint_pooled(
+			Origin::signed(ALICE),
+			vec![(BOB, dollar(1))]
+		));
+		assert_ok!(HomaLite::adjust_pending(Origin::signed(ROOT), ALICE, BatchIndex(2), 0));
+		assert_eq!(PooledShares::<Runtime>::iter_prefix((BatchIndex(2), ALICE)).count(), 0);
+	});
+}
+
+#[test]
+fn claim_and_restake_rejects_routed_claims() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::set_mint_router(Origin::signed(ROOT), Some(BOB)));
+
+		assert_noop!(
+			HomaLite::claim_and_restake(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::CannotRestakeRoutedClaim
+		);
+	});
+}
+
+#[test]
+fn claim_fails_for_batch_with_zero_exchange_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Remove all Liquid currency so the first batch is issued at the initial exchange rate,
+		// configured here to be zero.
+		InitialExchangeRate::set(Ratio::zero());
+		assert_ok!(HomaLite::governance_burn_liquid(
+			Origin::signed(ROOT),
+			ROOT,
+			dollar(INITIAL_BALANCE)
+		));
+		assert_eq!(Currencies::total_issuance(LKSM), 0);
+
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			HomaLite::batch_total_issuance_info(BatchIndex(0)),
+			Some(TotalIssuanceInfo {
+				staking_total: dollar(1000),
+				liquid_total: 0,
+			})
+		);
+
+		assert_noop!(
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::ZeroExchangeRate
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), dollar(1));
+	});
+}
+
+#[test]
+fn large_mints_require_confirmation() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		// Only governance can set the threshold.
+		assert_noop!(
+			HomaLite::set_large_mint_threshold(Origin::signed(ALICE), Some(dollar(500))),
+			BadOrigin
+		);
+		assert_ok!(HomaLite::set_large_mint_threshold(
+			Origin::signed(ROOT),
+			Some(dollar(500))
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LargeMintThresholdSet(Some(dollar(500))))
+		);
+
+		// Mints above the threshold are held for confirmation.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LargeMintPending(BatchIndex(0), ALICE, dollar(1000)))
+		);
+		assert_eq!(
+			Currencies::free_balance(KSM, &ALICE),
+			dollar(INITIAL_BALANCE) - dollar(1000)
+		);
+		assert_eq!(HomaLite::unconfirmed_mints(BatchIndex(0), ALICE), dollar(1000));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), 0);
+		assert_eq!(HomaLite::total_pending_staking(), 0);
+
+		// Mints up to the threshold are unaffected.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(500)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), BOB), dollar(500));
+		assert_eq!(HomaLite::unconfirmed_mints(BatchIndex(0), BOB), 0);
+
+		// Only governance can confirm, and only existing unconfirmed mints.
+		assert_noop!(
+			HomaLite::confirm_large_mint(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			BadOrigin
+		);
+		assert_noop!(
+			HomaLite::confirm_large_mint(Origin::signed(ROOT), BOB, BatchIndex(0)),
+			Error::<Runtime>::NoUnconfirmedMint
+		);
+
+		// Confirming after the batch is processed adds the mint to the current batch.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::confirm_large_mint(Origin::signed(ROOT), ALICE, BatchIndex(0)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LargeMintConfirmed(
+				BatchIndex(0),
+				BatchIndex(1),
+				ALICE,
+				dollar(1000)
+			))
+		);
+		assert_eq!(HomaLite::unconfirmed_mints(BatchIndex(0), ALICE), 0);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), 0);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), ALICE), dollar(1000));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(1500));
+	});
+}
+
+#[test]
+fn large_pooled_mints_are_held_with_their_shares() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_large_mint_threshold(
+			Origin::signed(ROOT),
+			Some(dollar(500))
+		));
+
+		assert_ok!(HomaLite::request_mint_pooled(
+			Origin::signed(ALICE),
+			vec![(BOB, dollar(400)), (ROOT, dollar(200))]
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PooledMintRequested(BatchIndex(0), ALICE, 2, dollar(600)))
+		);
+		assert_eq!(HomaLite::unconfirmed_mints(BatchIndex(0), ALICE), dollar(600));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), 0);
+		assert_eq!(PooledShares::<Runtime>::iter_prefix((BatchIndex(0), ALICE)).count(), 0);
+		assert_eq!(
+			HomaLite::unconfirmed_pooled_shares((BatchIndex(0), ALICE), &BOB),
+			dollar(400)
+		);
+
+		// Confirming moves the shares with the pending amount.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::confirm_large_mint(Origin::signed(ROOT), ALICE, BatchIndex(0)));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), ALICE), dollar(600));
+		assert_eq!(
+			UnconfirmedPooledShares::<Runtime>::iter_prefix((BatchIndex(0), ALICE)).count(),
+			0
+		);
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(1), ALICE), &BOB), dollar(400));
+		assert_eq!(HomaLite::pooled_shares((BatchIndex(1), ALICE), &ROOT), dollar(200));
+	});
+}
+
+#[test]
+fn reject_large_mint_refunds_the_user() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_large_mint_threshold(
+			Origin::signed(ROOT),
+			Some(dollar(500))
+		));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1000)));
+		assert_ok!(HomaLite::request_mint_pooled(
+			Origin::signed(BOB),
+			vec![(ALICE, dollar(600))]
+		));
+
+		// Only governance can reject, and only existing unconfirmed mints.
+		assert_noop!(
+			HomaLite::reject_large_mint(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			BadOrigin
+		);
+		assert_noop!(
+			HomaLite::reject_large_mint(Origin::signed(ROOT), ALICE, BatchIndex(1)),
+			Error::<Runtime>::NoUnconfirmedMint
+		);
+
+		assert_ok!(HomaLite::reject_large_mint(Origin::signed(ROOT), ALICE, BatchIndex(0)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LargeMintRejected(BatchIndex(0), ALICE, dollar(1000)))
+		);
+		assert_eq!(HomaLite::unconfirmed_mints(BatchIndex(0), ALICE), 0);
+		assert_eq!(Currencies::free_balance(KSM, &ALICE), dollar(INITIAL_BALANCE));
+
+		// Rejecting a pooled mint refunds the pool, and removes the shares.
+		assert_ok!(HomaLite::reject_large_mint(Origin::signed(ROOT), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(KSM, &BOB), dollar(INITIAL_BALANCE));
+		assert_eq!(
+			UnconfirmedPooledShares::<Runtime>::iter_prefix((BatchIndex(0), BOB)).count(),
+			0
+		);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), 0);
+	});
+}
+
+#[test]
+fn request_mint_and_claim_leaves_unprocessed_batches_pending() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint_and_claim(Origin::signed(ALICE), dollar(1)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(0),
+				ALICE,
+				dollar(1),
+				dollar(1),
+				Default::default()
+			))
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), dollar(1));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), 0);
+	});
+}
+
+#[test]
+fn request_mint_and_claim_claims_processed_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		// The current batch is already processed, as on a chain that processes batches immediately.
+		BatchTotalIssuanceInfo::<Runtime>::insert(
+			BatchIndex(0),
+			TotalIssuanceInfo {
+				staking_total: dollar(1),
+				liquid_total: dollar(10),
+			},
+		);
+
+		assert_ok!(HomaLite::request_mint_and_claim(Origin::signed(ALICE), dollar(1)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, dollar(10)))
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &ALICE), 0);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(10));
+	});
+}
+
+#[test]
+fn instant_batch_processes_mints_immediately() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_noop!(HomaLite::set_instant_batch(Origin::signed(ALICE), true), BadOrigin);
+		assert_ok!(HomaLite::set_instant_batch(Origin::signed(ROOT), true));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::InstantBatchSet(true))
+		);
+
+		// The first batch is processed at the initial exchange rate.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BatchProcessed(BatchIndex(0), dollar(1), dollar(10)))
+		);
+		// The batch is closed the same way `issue` closes it.
+		let rate = Ratio::saturating_from_integer(10);
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::RateChanged(RateChangeCause::Issue, None, Some(rate)))));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::BatchClosed(
+				BatchIndex(0),
+				vec![ALICE].try_into().unwrap(),
+				false
+			))));
+		assert_eq!(ProcessedBatches::get(), vec![(BatchIndex(0), rate)]);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(1));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(10));
+
+		// Later batches are processed at the most recent rate, and can be claimed right away.
+		assert_ok!(HomaLite::request_mint_and_claim(Origin::signed(BOB), dollar(2)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(1), BOB, dollar(20)))
+		);
+		assert_eq!(HomaLite::current_batch(), BatchIndex(2));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(20));
+
+		// Once turned off, mints are left pending until the batch is issued.
+		assert_ok!(HomaLite::set_instant_batch(Origin::signed(ROOT), false));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_eq!(HomaLite::current_batch(), BatchIndex(2));
+		assert_eq!(HomaLite::batch_total_issuance_info(BatchIndex(2)), None);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(2), &BOB), dollar(1));
+	});
+}
+
+#[test]
+fn instant_batch_issues_split_batches_for_their_own_totals() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(2)));
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::set_instant_batch(Origin::signed(ROOT), true));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_eq!(
+			HomaLite::batch_total_issuance_info(BatchIndex(0)),
+			Some(TotalIssuanceInfo {
+				staking_total: dollar(2),
+				liquid_total: dollar(20),
+			})
+		);
+		assert_eq!(
+			HomaLite::batch_total_issuance_info(BatchIndex(1)),
+			Some(TotalIssuanceInfo {
+				staking_total: dollar(1),
+				liquid_total: dollar(10),
+			})
+		);
+
+		// Both batches can be claimed in full.
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(1)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(20));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(10));
+	});
+}
+
+#[test]
+fn claim_many_claims_for_many_users() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+
+		assert_noop!(
+			HomaLite::claim_many(Origin::signed(ROOT), vec![(ALICE, BatchIndex(0)); 4], 4),
+			Error::<Runtime>::TooManyClaims
+		);
+
+		// Claims stop once `max` claims have been made.
+		assert_ok!(HomaLite::claim_many(
+			Origin::signed(ROOT),
+			vec![(ALICE, BatchIndex(0)), (BOB, BatchIndex(0))],
+			1
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::ClaimedMany(ROOT, 1))
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), &BOB), dollar(1));
+
+		// Already claimed and unprocessed pairs are skipped.
+		assert_ok!(HomaLite::claim_many(
+			Origin::signed(ROOT),
+			vec![(ALICE, BatchIndex(0)), (BOB, BatchIndex(1)), (BOB, BatchIndex(0))],
+			3
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::ClaimedMany(ROOT, 1))
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(1000));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &BOB), dollar(1));
+
+		// Pairs the keeper is not permitted to claim are skipped.
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), false));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim_many(
+			Origin::signed(ALICE),
+			vec![(BOB, BatchIndex(1))],
+			1
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::ClaimedMany(ALICE, 0))
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &BOB), dollar(1));
+	});
+}
+
+#[test]
+fn claims_are_auto_deposited_for_opted_in_users() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_auto_deposit(Origin::signed(ALICE), true));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::AutoDepositSet(ALICE, true))
+		);
+		assert!(HomaLite::auto_deposit(&ALICE));
+		assert!(!HomaLite::auto_deposit(&BOB));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Opted in: the claimed Liquid currency is moved into the savings position.
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::ClaimAutoDeposited(BatchIndex(0), ALICE, dollar(1000)))));
+		assert_eq!(ClaimDeposits::get(), vec![(ALICE, dollar(1000))]);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), 0);
+		assert_eq!(Currencies::free_balance(LKSM, &SAVINGS_ACCOUNT), dollar(1000));
+
+		// Opted out: the claimed Liquid currency stays in the free balance.
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(ClaimDeposits::get(), vec![(ALICE, dollar(1000))]);
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(1000));
+
+		assert_ok!(HomaLite::set_auto_deposit(Origin::signed(ALICE), false));
+		assert!(!HomaLite::auto_deposit(&ALICE));
+	});
+}
+
+#[test]
+fn auto_deposit_is_only_reported_when_the_handler_deposits() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_auto_deposit(Origin::signed(ALICE), true));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// The handler takes nothing, so the Liquid currency stays with the user and no deposit
+		// is reported.
+		ClaimDepositsAccepted::set(false);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, Event::HomaLite(crate::Event::ClaimAutoDeposited(..)))));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, dollar(1000)))
+		);
+		assert_eq!(ClaimDeposits::get(), vec![]);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(Currencies::free_balance(LKSM, &SAVINGS_ACCOUNT), 0);
+	});
+}
+
+#[test]
+fn claim_all_claims_every_processed_batch() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+
+		assert_ok!(HomaLite::claim_all(Origin::signed(ALICE)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(1), ALICE, dollar(1000)))
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(2000));
+		// The pending amount in the unprocessed batch is left as it is.
+		assert_eq!(HomaLite::pending_batches(&ALICE).into_inner(), vec![BatchIndex(2)]);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(2), &ALICE), dollar(1));
+
+		// Nothing left to claim.
+		assert_ok!(HomaLite::claim_all(Origin::signed(ALICE)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(2000));
+	});
+}
+
+#[test]
+fn claims_are_auto_compounded_for_opted_in_users() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_auto_compound(Origin::signed(ALICE), true));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::AutoCompoundSet(ALICE, true))
+		);
+		assert!(HomaLite::auto_compound(&ALICE));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Enabled: half of the claimed Liquid currency is restaked into the current batch.
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, dollar(1000)))
+		);
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::AutoCompounded(
+				BatchIndex(0),
+				ALICE,
+				dollar(500),
+				dollar(1) / 2
+			))));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(1),
+				ALICE,
+				dollar(1) / 2,
+				dollar(1) / 2,
+				Default::default()
+			))));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(500));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &ALICE), dollar(1) / 2);
+		// The restaked half is already staked, so the stash only holds the processed deposits.
+		assert_eq!(HomaLite::restaked_pending(BatchIndex(1), &ALICE), dollar(1) / 2);
+		assert_eq!(Currencies::free_balance(KSM, &RELAY_CHAIN_STASH), dollar(2));
+		assert_eq!(HomaLite::reconcile(), Ok((0, dollar(2))));
+
+		// Disabled: the whole claim stays in the free balance.
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(1000));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &BOB), 0);
+		assert_eq!(HomaLite::reconcile(), Ok((0, dollar(2))));
+
+		assert_ok!(HomaLite::set_auto_compound(Origin::signed(ALICE), false));
+		assert!(!HomaLite::auto_compound(&ALICE));
+	});
+}
+
+#[test]
+fn auto_compound_falls_back_to_the_users_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::set_auto_compound(Origin::signed(ALICE), true));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// The current batch is full, so the restake would exceed the batch's pending cap.
+		MaxPendingPerBatch::set(dollar(1));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::AutoCompoundFailed(BatchIndex(0), ALICE, dollar(500)))));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::LiquidCurrencyClaimed(BatchIndex(0), ALICE, dollar(1000)))
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), &ALICE), 0);
+	});
+}
+
+#[test]
+fn sponsor_claim_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_noop!(
+			HomaLite::sponsor_claim(Origin::signed(BOB), ALICE, BatchIndex(0)),
+			Error::<Runtime>::LiquidCurrencyNotIssuedForThisBatch
+		);
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Cannot sponsor a claim that does not exist.
+		assert_noop!(
+			HomaLite::sponsor_claim(Origin::signed(ALICE), BOB, BatchIndex(0)),
+			Error::<Runtime>::NoPendingAmount
+		);
+
+		// Sponsoring is subject to the same permissions as claiming.
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), false));
+		assert_noop!(
+			HomaLite::sponsor_claim(Origin::signed(BOB), ALICE, BatchIndex(0)),
+			Error::<Runtime>::ClaimNotPermitted
+		);
+		assert_ok!(HomaLite::set_permissionless_claim(Origin::signed(ROOT), true));
+
+		assert_ok!(HomaLite::sponsor_claim(Origin::signed(BOB), ALICE, BatchIndex(0)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::ClaimSponsored(BatchIndex(0), BOB, ALICE, dollar(1000)))
+		);
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), 0);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), 0);
+	});
+}
+
+#[test]
+fn first_batch_is_issued_at_initial_exchange_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Remove all Liquid currency, as on a new chain.
+		assert_ok!(HomaLite::governance_burn_liquid(
+			Origin::signed(ROOT),
+			ROOT,
+			dollar(INITIAL_BALANCE)
+		));
+		assert_eq!(Currencies::total_issuance(LKSM), 0);
+
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			HomaLite::batch_total_issuance_info(BatchIndex(0)),
+			Some(TotalIssuanceInfo {
+				staking_total: dollar(1000),
+				liquid_total: dollar(10_000),
+			})
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(10));
+
+		// Once there is Liquid currency in circulation, the computed rate is used.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(2)));
+		assert_eq!(
+			HomaLite::batch_total_issuance_info(BatchIndex(1)),
+			Some(TotalIssuanceInfo {
+				staking_total: dollar(2),
+				liquid_total: dollar(10),
+			})
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(1)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(5));
+	});
+}
+
+#[test]
+fn migrate_pending_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let rate = Ratio::saturating_from_rational(1000, 1);
+		assert_eq!(HomaLite::export_pending(&ALICE, BatchIndex(0)), (dollar(1), rate));
+
+		// Migration must be enabled by governance.
+		assert_noop!(
+			HomaLite::migrate_pending(&ALICE, BatchIndex(0)),
+			Error::<Runtime>::MigrationNotEnabled
+		);
+		assert_noop!(HomaLite::set_migration_enabled(Origin::signed(ALICE), true), BadOrigin);
+		assert_ok!(HomaLite::set_migration_enabled(Origin::signed(ROOT), true));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MigrationEnabledSet(true))
+		);
+
+		// Migrate only Alice's pending amount.
+		assert_eq!(HomaLite::migrate_pending(&ALICE, BatchIndex(0)), Ok((dollar(1), rate)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::PendingMigrated(BatchIndex(0), ALICE, dollar(1)))
+		);
+		assert_eq!(HomaLite::pending_amount(BatchIndex(0), ALICE), 0);
+		assert!(!HomaLite::pending_batches(ALICE).contains(&BatchIndex(0)));
+		assert_eq!(HomaLite::total_pending_staking(), dollar(1));
+		assert_noop!(
+			HomaLite::migrate_pending(&ALICE, BatchIndex(0)),
+			Error::<Runtime>::NoPendingAmount
+		);
+
+		// Pending amounts that were not migrated are unaffected.
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(1000));
+
+		// Unprocessed batches are exported with a zero rate.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_eq!(
+			HomaLite::export_pending(&BOB, BatchIndex(1)),
+			(dollar(1), Ratio::zero())
+		);
+	});
+}
+
+#[test]
+fn blocklisted_accounts_cannot_mint_or_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+
+		// Only governance can manage the blocklist.
+		assert_noop!(HomaLite::add_to_blocklist(Origin::signed(BOB), ALICE), BadOrigin);
+		assert_ok!(HomaLite::add_to_blocklist(Origin::signed(ROOT), ALICE));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BlocklistAdded(ALICE))
+		);
+		assert!(HomaLite::is_blocked(ALICE));
+
+		// Blocked accounts can neither mint nor claim, nor be minted for.
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(ALICE), dollar(1)),
+			Error::<Runtime>::AccountBlocked
+		);
+		assert_noop!(
+			HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)),
+			Error::<Runtime>::AccountBlocked
+		);
+		assert_noop!(
+			HomaLite::request_mint_batch(Origin::signed(BOB), vec![(ALICE, dollar(1))]),
+			Error::<Runtime>::AccountBlocked
+		);
+
+		// Other accounts are unaffected.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+
+		assert_noop!(HomaLite::remove_from_blocklist(Origin::signed(BOB), ALICE), BadOrigin);
+		assert_ok!(HomaLite::remove_from_blocklist(Origin::signed(ROOT), ALICE));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BlocklistRemoved(ALICE))
+		);
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+	});
+}
+
+#[test]
+fn claim_history_records_claims_in_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		let rate = Ratio::saturating_from_rational(1000, 1);
+		for batch in 0..4 {
+			assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+			let lksm_issuance = Currencies::total_issuance(LKSM);
+			assert_ok!(HomaLite::issue(Origin::signed(ROOT), lksm_issuance / 1000));
+			assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(batch)));
+		}
+
+		// Only the most recent `MaxClaimHistory` claims are kept, oldest first.
+		assert_eq!(
+			HomaLite::claim_history(ALICE).into_inner(),
+			vec![
+				(BatchIndex(1), dollar(1000), rate),
+				(BatchIndex(2), dollar(1000), rate),
+				(BatchIndex(3), dollar(1000), rate),
+			]
+		);
+		assert!(HomaLite::claim_history(BOB).is_empty());
+
+		// Claims without a pending amount do not evict the recorded claims.
+		let history = HomaLite::claim_history(ALICE);
+		let events = System::events().len();
+		for batch in 0..4 {
+			assert_ok!(HomaLite::claim(Origin::signed(BOB), ALICE, BatchIndex(batch)));
+		}
+		assert_eq!(HomaLite::claim_history(ALICE), history);
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn increasing_pending_reports_the_cap_hit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		MaxPendingPerUser::set(dollar(10));
+		MaxPendingPerBatch::set(dollar(15));
+
+		// Increasing up to the per-user cap is allowed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(6)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(4)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::MintRequested(
+				BatchIndex(0),
+				ALICE,
+				dollar(4),
+				dollar(10),
+				Default::default()
+			))
+		);
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(ALICE), 1),
+			Error::<Runtime>::ExceededUserPendingCap
+		);
+
+		// Increasing up to the per-batch cap is allowed.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(5)));
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(0)), dollar(15));
+		assert_noop!(
+			HomaLite::request_mint(Origin::signed(BOB), 1),
+			Error::<Runtime>::ExceededBatchPendingCap
+		);
+
+		// Rerolling into a full batch hits the same caps.
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(6)));
+		assert_noop!(
+			HomaLite::reroll_pending(Origin::signed(BOB), BatchIndex(0)),
+			Error::<Runtime>::ExceededUserPendingCap
+		);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(6)));
+		assert_noop!(
+			HomaLite::reroll_pending(Origin::signed(ALICE), BatchIndex(0)),
+			Error::<Runtime>::ExceededUserPendingCap
+		);
+		MaxPendingPerUser::set(dollar(20));
+		MaxPendingPerBatch::set(dollar(17));
+		assert_noop!(
+			HomaLite::reroll_pending(Origin::signed(ALICE), BatchIndex(0)),
+			Error::<Runtime>::ExceededBatchPendingCap
+		);
+		assert_ok!(HomaLite::reroll_pending(Origin::signed(BOB), BatchIndex(0)));
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(0)), dollar(10));
+		assert_eq!(HomaLite::batch_pending_total(BatchIndex(1)), dollar(17));
+		assert_eq!(HomaLite::pending_amount(BatchIndex(1), BOB), dollar(11));
+	});
+}
+
+#[test]
+fn exchange_rate_is_read_from_recorded_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_eq!(HomaLite::exchange_rate(), None);
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let first_rate = Ratio::saturating_from_integer(1000);
+		assert_eq!(HomaLite::exchange_rate(), Some(first_rate));
+
+		// Changes to the Liquid currency's issuance do not affect the rate until the next batch is
+		// processed.
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(HomaLite::exchange_rate(), Some(first_rate));
+
+		// Unprocessed split batches are skipped.
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_eq!(HomaLite::current_batch(), BatchIndex(2));
+		assert_eq!(HomaLite::exchange_rate(), Some(first_rate));
+		assert_eq!(HomaLite::protocol_status().exchange_rate, Some(first_rate));
+
+		let liquid_total = Currencies::total_issuance(LKSM);
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), liquid_total / 800));
+		let second_rate = Ratio::saturating_from_integer(800);
+		assert_eq!(HomaLite::exchange_rate(), Some(second_rate));
+
+		// The rates of earlier batches are kept as they were when processed.
+		assert_eq!(HomaLite::batch_exchange_rate(BatchIndex(0)), Some(first_rate));
+		assert_eq!(HomaLite::batch_exchange_rate(BatchIndex(1)), Some(second_rate));
+	});
+}
+
+#[test]
+fn emergency_rebase_adjusts_future_claims() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+
+		let factor = Ratio::saturating_from_rational(9, 10);
+		assert_noop!(HomaLite::emergency_rebase(Origin::signed(ALICE), factor), BadOrigin);
+		assert_noop!(
+			HomaLite::emergency_rebase(Origin::signed(ROOT), Ratio::zero()),
+			Error::<Runtime>::InvalidRebaseFactor
+		);
+		assert_ok!(HomaLite::emergency_rebase(Origin::signed(ROOT), factor));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::EmergencyRebase(factor))
+		);
+		assert_eq!(HomaLite::rebase_factor(), factor);
+
+		// Claims after the rebase use the adjusted rate. Existing balances are unchanged.
+		assert_eq!(HomaLite::simulate_claim(&BOB, BatchIndex(0), dollar(1001)), dollar(900));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &BOB), dollar(900));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(1000));
+		assert_eq!(
+			HomaLite::claim_history(BOB).into_inner(),
+			vec![(BatchIndex(0), dollar(900), Ratio::saturating_from_integer(900))]
+		);
+	});
+}
+
+#[test]
+fn rate_changes_emit_rate_changed_with_their_cause() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let rate = HomaLite::exchange_rate().unwrap();
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::RateChanged(RateChangeCause::Issue, None, Some(rate)))));
+
+		let factor = Ratio::saturating_from_rational(9, 10);
+		assert_ok!(HomaLite::emergency_rebase(Origin::signed(ROOT), factor));
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::RateChanged(
+				RateChangeCause::EmergencyRebase,
+				Some(rate),
+				Some(rate.saturating_mul(factor))
+			))));
+		assert_eq!(HomaLite::effective_exchange_rate(), Some(rate.saturating_mul(factor)));
+
+		// Later batches report the rebased rate as the old one.
+		System::reset_events();
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		let new_rate = HomaLite::exchange_rate().unwrap();
+		assert!(System::events().iter().any(|record| record.event
+			== Event::HomaLite(crate::Event::RateChanged(
+				RateChangeCause::Issue,
+				Some(rate.saturating_mul(factor)),
+				Some(new_rate.saturating_mul(factor))
+			))));
+	});
+}
+
+#[test]
+fn simulate_claim_uses_the_rate_issue_would() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+
+		// Without any Liquid currency, batches are issued at the initial exchange rate.
+		assert_ok!(HomaLite::governance_burn_liquid(
+			Origin::signed(ROOT),
+			ROOT,
+			dollar(INITIAL_BALANCE)
+		));
+		assert_eq!(
+			HomaLite::simulate_claim(&ALICE, BatchIndex(0), dollar(1000)),
+			dollar(10)
+		);
+
+		// The bootstrap rate overrides the computed rate.
+		BootstrapRate::set(Some(Ratio::saturating_from_integer(5)));
+		BootstrapBatches::set(1);
+		assert_eq!(HomaLite::simulate_claim(&ALICE, BatchIndex(0), dollar(1000)), dollar(5));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(Currencies::free_balance(LKSM, &ALICE), dollar(5));
+	});
+}
+
+#[test]
+fn bootstrap_batches_are_issued_at_the_bootstrap_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		BootstrapRate::set(Some(Ratio::saturating_from_integer(5)));
+		BootstrapBatches::set(2);
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			HomaLite::batch_exchange_rate(BatchIndex(0)),
+			Some(Ratio::saturating_from_integer(5))
+		);
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, Event::HomaLite(crate::Event::BootstrapEnded(_)))));
+
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			HomaLite::batch_exchange_rate(BatchIndex(1)),
+			Some(Ratio::saturating_from_integer(5))
+		);
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::HomaLite(crate::Event::BootstrapEnded(BatchIndex(1)))
+		);
+		assert_eq!(HomaLite::processed_batch_count(), 2);
+
+		// After the bootstrap the rate is computed from the total issuances.
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(
+			HomaLite::batch_exchange_rate(BatchIndex(2)),
+			Ratio::checked_from_rational(Currencies::total_issuance(LKSM), dollar(1000))
+		);
+		assert_ne!(
+			HomaLite::batch_exchange_rate(BatchIndex(2)),
+			Some(Ratio::saturating_from_integer(5))
+		);
+	});
+}
+
+#[test]
+fn settled_mints_are_recorded_and_pruned() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_eq!(HomaLite::settled_mints(BatchIndex(0), ALICE), None);
+
+		// Cancelled by governance.
+		assert_ok!(HomaLite::adjust_pending(Origin::signed(ROOT), BOB, BatchIndex(0), 0));
+		assert_eq!(
+			HomaLite::settled_mints(BatchIndex(0), BOB),
+			Some(SettlementKind::Cancelled)
+		);
+		// Minting into the batch again clears the settlement.
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+
+		// Refunded.
+		assert_ok!(HomaLite::split_current_batch(Origin::signed(ROOT)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::force_refund_batch(Origin::signed(ROOT), BatchIndex(1), 10));
+		assert_eq!(
+			HomaLite::settled_mints(BatchIndex(1), BOB),
+			Some(SettlementKind::Refunded)
+		);
+
+		// Claimed.
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_eq!(
+			HomaLite::settled_mints(BatchIndex(0), ALICE),
+			Some(SettlementKind::Claimed)
+		);
+
+		// Settlements are pruned `SettledMintsRetention` batches later.
+		for _ in 0..9 {
+			assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		}
+		assert_eq!(HomaLite::current_batch(), BatchIndex(11));
+		assert_eq!(HomaLite::settled_mints(BatchIndex(0), ALICE), None);
+		assert_eq!(
+			HomaLite::settled_mints(BatchIndex(1), BOB),
+			Some(SettlementKind::Refunded)
+		);
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::settled_mints(BatchIndex(1), BOB), None);
+	});
+}
+
+#[test]
+fn settled_mints_pruning_is_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(HomaLite::set_stash_account_id(Origin::signed(ROOT), RELAY_CHAIN_STASH));
+		MaxSettledMintsPruned::set(1);
+		assert_ok!(HomaLite::request_mint(Origin::signed(ALICE), dollar(1)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		assert_ok!(HomaLite::issue(Origin::signed(ROOT), dollar(1000)));
+		assert_ok!(HomaLite::claim(Origin::signed(ALICE), ALICE, BatchIndex(0)));
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(0)));
+		assert_ok!(HomaLite::request_mint(Origin::signed(BOB), dollar(1)));
+		for _ in 0..10 {
+			assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		}
+		assert_eq!(HomaLite::current_batch(), BatchIndex(11));
+
+		// Only one settlement is pruned per issue.
+		assert_eq!(SettledMints::<Runtime>::iter_prefix(BatchIndex(0)).count(), 1);
+		assert_eq!(HomaLite::settled_mints_prune_cursor(), BatchIndex(0));
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(SettledMints::<Runtime>::iter_prefix(BatchIndex(0)).count(), 0);
+		assert_eq!(HomaLite::settled_mints_prune_cursor(), BatchIndex(1));
+		assert_ok!(HomaLite::force_issue(Origin::signed(ROOT), dollar(1000)));
+		assert_eq!(HomaLite::settled_mints_prune_cursor(), BatchIndex(2));
+
+		// Settlements of pruned batches are not recorded.
+		assert_ok!(HomaLite::claim(Origin::signed(BOB), BOB, BatchIndex(1)));
+		assert_eq!(HomaLite::settled_mints(BatchIndex(1), BOB), None);
+	});
+}
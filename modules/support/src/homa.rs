@@ -19,7 +19,7 @@
 use super::*;
 use frame_support::{traits::Get, Parameter};
 use sp_runtime::{
-	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member},
+	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
 	RuntimeDebug,
 };
 
@@ -28,6 +28,33 @@ pub trait OnNewEra<EraIndex> {
 	fn on_new_era(era: EraIndex);
 }
 
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnBatchProcessed<BatchIndex, Rate> {
+	fn on_batch_processed(batch: BatchIndex, rate: Rate);
+}
+
+/// Drains pending amounts from a module being migrated away from, such as homa-lite when moving to
+/// the full homa module.
+pub trait OnMigrate<AccountId, BatchIndex, Balance, Rate> {
+	/// Removes the user's pending amount in a batch, returning the amount and the batch's exchange
+	/// rate. The rate is zero if the batch has not been processed.
+	fn migrate_pending(who: &AccountId, batch: BatchIndex) -> sp_std::result::Result<(Balance, Rate), DispatchError>;
+}
+
+/// Moves claimed Liquid currency into an external savings position, such as an incentive pool,
+/// for users who opted in.
+pub trait OnClaimDeposit<AccountId, Balance> {
+	/// Deposits `amount` of Liquid currency, already in `who`'s free balance, into the position.
+	/// Returns the amount actually deposited, which is zero if nothing was moved.
+	fn on_claim_deposit(who: &AccountId, amount: Balance) -> sp_std::result::Result<Balance, DispatchError>;
+}
+
+impl<AccountId, Balance: Zero> OnClaimDeposit<AccountId, Balance> for () {
+	fn on_claim_deposit(_who: &AccountId, _amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		Ok(Zero::zero())
+	}
+}
+
 pub trait NomineesProvider<AccountId> {
 	fn nominees() -> Vec<AccountId>;
 }
@@ -40,8 +40,9 @@ use sp_std::{
 pub mod homa;
 pub mod mocks;
 pub use homa::{
-	HomaProtocol, NomineesProvider, OnCommission, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk,
+	HomaProtocol, NomineesProvider, OnBatchProcessed, OnClaimDeposit, OnCommission, OnMigrate, OnNewEra,
+	PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger,
+	PolkadotUnlockChunk,
 };
 
 pub type Price = FixedU128;
@@ -252,6 +252,8 @@ parameter_types! {
 	pub const MaxCandidates: u32 = 200;
 	pub const MaxInvulnerables: u32 = 50;
 	pub const KickPenaltySessionLength: u32 = 8;
+	pub const CollatorRewardGracePeriod: u32 = 1;
+	pub const MaxRecentlyKickedCollators: u32 = 20;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(50);
 }
 
@@ -266,6 +268,8 @@ impl module_collator_selection::Config for Runtime {
 	type MaxInvulnerables = MaxInvulnerables;
 	type KickPenaltySessionLength = KickPenaltySessionLength;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type RewardGracePeriod = CollatorRewardGracePeriod;
+	type MaxRecentlyKicked = MaxRecentlyKickedCollators;
 	type WeightInfo = weights::module_collator_selection::WeightInfo<Runtime>;
 }
 
@@ -1842,6 +1846,83 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_collator_selection_rpc_runtime_api::CollatorSelectionApi<Block, AccountId> for Runtime {
+		fn recently_kicked() -> Vec<(AccountId, u32)> {
+			CollatorSelection::recently_kicked().into_inner()
+		}
+	}
+
+	impl module_homa_lite_rpc_runtime_api::HomaLiteApi<Block, AccountId, Balance> for Runtime {
+		fn batch_issuance_info(
+			_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+		) -> Option<module_homa_lite_rpc_runtime_api::TotalIssuanceInfo> {
+			None
+		}
+
+		fn pending_mints(_who: AccountId) -> Vec<(module_homa_lite_rpc_runtime_api::BatchIndex, Balance)> {
+			Vec::new()
+		}
+
+		fn pending_mints_paged(
+			_who: AccountId,
+			_start_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+			_limit: u32,
+		) -> Vec<(module_homa_lite_rpc_runtime_api::BatchIndex, Balance)> {
+			Vec::new()
+		}
+
+		fn simulate_claim(
+			_who: AccountId,
+			_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+			_staking_total: Balance,
+		) -> Balance {
+			0
+		}
+
+		fn total_pending_staking() -> Balance {
+			0
+		}
+
+		fn claimable_batches(
+			_start_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+			_limit: u32,
+		) -> Vec<module_homa_lite_rpc_runtime_api::BatchIndex> {
+			Vec::new()
+		}
+
+		fn protocol_status() -> module_homa_lite_rpc_runtime_api::ProtocolStatus {
+			Default::default()
+		}
+
+		fn exchange_rate() -> Option<module_homa_lite_rpc_runtime_api::Ratio> {
+			None
+		}
+
+		fn realized_yield(
+			_who: AccountId,
+			_since_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+		) -> Option<module_homa_lite_rpc_runtime_api::Ratio> {
+			None
+		}
+
+		fn claim_history(
+			_who: AccountId,
+		) -> Vec<(module_homa_lite_rpc_runtime_api::BatchIndex, Balance, module_homa_lite_rpc_runtime_api::Ratio)> {
+			Vec::new()
+		}
+
+		fn mint_settlement(
+			_who: AccountId,
+			_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+		) -> Option<module_homa_lite_rpc_runtime_api::SettlementKind> {
+			None
+		}
+
+		fn total_claimable(_who: AccountId) -> Balance {
+			0
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -18,7 +18,7 @@
 
 use crate::{
 	AccountId, Balance, Balances, CollatorKickThreshold, CollatorSelection, Event, MaxCandidates, MaxInvulnerables,
-	MinCandidates, Period, Runtime, Session, SessionKeys, System,
+	MinCandidates, Period, Permill, Runtime, Session, SessionKeys, System,
 };
 
 use frame_benchmarking::{account, whitelisted_caller};
@@ -76,6 +76,26 @@ runtime_benchmarks! {
 		assert_last_event(module_collator_selection::Event::NewInvulnerables(new_invulnerables).into());
 	}
 
+	add_invulnerable {
+		let b in 1 .. MaxInvulnerables::get();
+		let invulnerables = (0..b - 1).map(|c| account("candidate", c, SEED)).collect::<Vec<_>>();
+		CollatorSelection::set_invulnerables(RawOrigin::Root.into(), invulnerables)?;
+		let new_invulnerable: AccountId = account("candidate", b, SEED);
+	}: _(RawOrigin::Root, new_invulnerable.clone())
+	verify {
+		assert_last_event(module_collator_selection::Event::InvulnerableAdded(new_invulnerable).into());
+	}
+
+	remove_invulnerable {
+		let b in 1 .. MaxInvulnerables::get();
+		let invulnerables = (0..b).map(|c| account("candidate", c, SEED)).collect::<Vec<_>>();
+		CollatorSelection::set_invulnerables(RawOrigin::Root.into(), invulnerables.clone())?;
+		let leaving = invulnerables.last().unwrap().clone();
+	}: _(RawOrigin::Root, leaving.clone())
+	verify {
+		assert_last_event(module_collator_selection::Event::InvulnerableRemoved(leaving).into());
+	}
+
 	set_desired_candidates {
 		let max: u32 = MaxInvulnerables::get();
 	}: {
@@ -98,6 +118,29 @@ runtime_benchmarks! {
 		assert_last_event(module_collator_selection::Event::NewCandidacyBond(bond).into());
 	}
 
+	set_max_candidacy_bond {
+		let max: Balance = Balances::minimum_balance().checked_mul(100u32.into()).unwrap();
+		assert_ok!(CollatorSelection::set_candidacy_bond(RawOrigin::Root.into(), max.clone()));
+	}: {
+		assert_ok!(
+			CollatorSelection::set_max_candidacy_bond(RawOrigin::Root.into(), Some(max.clone()))
+		);
+	}
+	verify {
+		assert_last_event(module_collator_selection::Event::NewMaxCandidacyBond(Some(max)).into());
+	}
+
+	set_kick_threshold {
+		let threshold = Permill::from_percent(30);
+	}: {
+		assert_ok!(
+			CollatorSelection::set_kick_threshold(RawOrigin::Root.into(), threshold)
+		);
+	}
+	verify {
+		assert_last_event(module_collator_selection::Event::KickThresholdUpdated(threshold).into());
+	}
+
 	// worse case is when we have all the max-candidate slots filled except one, and we fill that
 	// one.
 	register_as_candidate {
@@ -118,6 +161,7 @@ runtime_benchmarks! {
 		assert_last_event(module_collator_selection::Event::CandidateAdded(caller, bond.checked_div(2u32.into()).unwrap()).into());
 	}
 
+	// worse case is a bonded candidate, as the bond is also reserved.
 	register_candidate {
 		let c in 1 .. MaxCandidates::get();
 
@@ -130,9 +174,9 @@ runtime_benchmarks! {
 		Balances::make_free_balance_be(&caller, bond.clone());
 
 		Session::set_keys(RawOrigin::Signed(caller.clone()).into(), SessionKeys::default(), vec![]).unwrap();
-	}: _(RawOrigin::Root, caller.clone())
+	}: _(RawOrigin::Root, caller.clone(), true)
 	verify {
-		assert_last_event(module_collator_selection::Event::CandidateAdded(caller, 0).into());
+		assert_last_event(module_collator_selection::Event::CandidateAdded(caller, bond.checked_div(2u32.into()).unwrap()).into());
 	}
 
 	// worse case is the last candidate leaving.
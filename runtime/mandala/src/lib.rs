@@ -173,6 +173,7 @@ parameter_types! {
 	// Ecosystem modules
 	pub const StarportPalletId: PalletId = PalletId(*b"aca/stpt");
 	pub const HomaLitePalletId: PalletId = PalletId(*b"aca/hmlt");
+	pub const HomaLiteIncentivePalletId: PalletId = PalletId(*b"aca/hlin");
 }
 
 pub fn get_all_module_accounts() -> Vec<AccountId> {
@@ -274,6 +275,8 @@ parameter_types! {
 	pub const MaxCandidates: u32 = 200;
 	pub const MaxInvulnerables: u32 = 50;
 	pub const KickPenaltySessionLength: u32 = 8;
+	pub const CollatorRewardGracePeriod: u32 = 1;
+	pub const MaxRecentlyKickedCollators: u32 = 20;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(50);
 }
 
@@ -288,6 +291,8 @@ impl module_collator_selection::Config for Runtime {
 	type MaxInvulnerables = MaxInvulnerables;
 	type KickPenaltySessionLength = KickPenaltySessionLength;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type RewardGracePeriod = CollatorRewardGracePeriod;
+	type MaxRecentlyKicked = MaxRecentlyKickedCollators;
 	type WeightInfo = weights::module_collator_selection::WeightInfo<Runtime>;
 }
 
@@ -1213,6 +1218,25 @@ impl module_homa::Config for Runtime {
 parameter_types! {
 	pub const KSM: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
 	pub const LKSM: CurrencyId = CurrencyId::Token(TokenSymbol::LKSM);
+	pub const MaxPendingBatchesPerUser: u32 = 10;
+	pub const MintCooldownBlocks: BlockNumber = 0;
+	pub const MaxMintRequestsPerCall: u32 = 50;
+	pub ReconciliationTolerance: Balance = dollar(KSM::get());
+	pub const MaxMemoLen: u32 = 32;
+	pub AutoPauseThreshold: Ratio = Ratio::saturating_from_rational(1, 10);
+	pub HomaLiteIncentiveAccount: AccountId = HomaLiteIncentivePalletId::get().into_account();
+	pub const MaxBatchSplits: u32 = 5;
+	pub const MaxAutoClaimsPerBlock: u32 = 20;
+	pub HomaLiteInitialExchangeRate: Ratio = Ratio::saturating_from_rational(10, 1);	// 1 : 10
+	pub const MaxClaimHistory: u32 = 20;
+	pub const MaxPendingPerUser: Balance = Balance::max_value();
+	pub const MaxPendingPerBatch: Balance = Balance::max_value();
+	pub const SettledMintsRetention: u32 = 100;
+	pub const MaxSettledMintsPruned: u32 = 100;
+	pub const HomaLiteBootstrapBatches: u32 = 0;
+	pub const MaxClaimsPerCall: u32 = 50;
+	pub HomaLiteAutoCompoundRatio: Ratio = Ratio::saturating_from_rational(1, 2);
+	pub const HomaLiteMaxParticipantsInEvent: u32 = 50;
 }
 impl module_homa_lite::Config for Runtime {
 	type Event = Event;
@@ -1223,6 +1247,28 @@ impl module_homa_lite::Config for Runtime {
 	type PalletId = HomaLitePalletId;
 	type IssuerOrigin = EnsureRootOrHalfGeneralCouncil;
 	type GovernanceOrigin = EnsureRootOrHalfGeneralCouncil;
+	type MaxPendingBatchesPerUser = MaxPendingBatchesPerUser;
+	type MintCooldownBlocks = MintCooldownBlocks;
+	type MaxMintRequestsPerCall = MaxMintRequestsPerCall;
+	type BatchHandler = ();
+	type ReconciliationTolerance = ReconciliationTolerance;
+	type MaxMemoLen = MaxMemoLen;
+	type AutoPauseThreshold = AutoPauseThreshold;
+	type IncentiveAccount = HomaLiteIncentiveAccount;
+	type MaxBatchSplits = MaxBatchSplits;
+	type MaxAutoClaimsPerBlock = MaxAutoClaimsPerBlock;
+	type InitialExchangeRate = HomaLiteInitialExchangeRate;
+	type MaxClaimHistory = MaxClaimHistory;
+	type MaxPendingPerUser = MaxPendingPerUser;
+	type MaxPendingPerBatch = MaxPendingPerBatch;
+	type SettledMintsRetention = SettledMintsRetention;
+	type MaxSettledMintsPruned = MaxSettledMintsPruned;
+	type BootstrapRateProvider = ();
+	type BootstrapBatches = HomaLiteBootstrapBatches;
+	type MaxClaimsPerCall = MaxClaimsPerCall;
+	type ClaimDepositHandler = ();
+	type AutoCompoundRatio = HomaLiteAutoCompoundRatio;
+	type MaxParticipantsInEvent = HomaLiteMaxParticipantsInEvent;
 }
 
 parameter_types! {
@@ -2112,6 +2158,83 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_collator_selection_rpc_runtime_api::CollatorSelectionApi<Block, AccountId> for Runtime {
+		fn recently_kicked() -> Vec<(AccountId, u32)> {
+			CollatorSelection::recently_kicked().into_inner()
+		}
+	}
+
+	impl module_homa_lite_rpc_runtime_api::HomaLiteApi<Block, AccountId, Balance> for Runtime {
+		fn batch_issuance_info(
+			batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+		) -> Option<module_homa_lite_rpc_runtime_api::TotalIssuanceInfo> {
+			HomaLite::batch_total_issuance_info(batch)
+		}
+
+		fn pending_mints(who: AccountId) -> Vec<(module_homa_lite_rpc_runtime_api::BatchIndex, Balance)> {
+			HomaLite::pending_mints(&who)
+		}
+
+		fn pending_mints_paged(
+			who: AccountId,
+			start_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+			limit: u32,
+		) -> Vec<(module_homa_lite_rpc_runtime_api::BatchIndex, Balance)> {
+			HomaLite::pending_mints_paged(&who, start_batch, limit)
+		}
+
+		fn simulate_claim(
+			who: AccountId,
+			batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+			staking_total: Balance,
+		) -> Balance {
+			HomaLite::simulate_claim(&who, batch, staking_total)
+		}
+
+		fn total_pending_staking() -> Balance {
+			HomaLite::total_pending_staking()
+		}
+
+		fn claimable_batches(
+			start_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+			limit: u32,
+		) -> Vec<module_homa_lite_rpc_runtime_api::BatchIndex> {
+			HomaLite::claimable_batches(start_batch, limit)
+		}
+
+		fn protocol_status() -> module_homa_lite_rpc_runtime_api::ProtocolStatus {
+			HomaLite::protocol_status()
+		}
+
+		fn exchange_rate() -> Option<module_homa_lite_rpc_runtime_api::Ratio> {
+			HomaLite::exchange_rate()
+		}
+
+		fn realized_yield(
+			who: AccountId,
+			since_batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+		) -> Option<module_homa_lite_rpc_runtime_api::Ratio> {
+			HomaLite::realized_yield(&who, since_batch)
+		}
+
+		fn claim_history(
+			who: AccountId,
+		) -> Vec<(module_homa_lite_rpc_runtime_api::BatchIndex, Balance, module_homa_lite_rpc_runtime_api::Ratio)> {
+			HomaLite::claim_history(who).into_inner()
+		}
+
+		fn mint_settlement(
+			who: AccountId,
+			batch: module_homa_lite_rpc_runtime_api::BatchIndex,
+		) -> Option<module_homa_lite_rpc_runtime_api::SettlementKind> {
+			HomaLite::settled_mints(batch, who)
+		}
+
+		fn total_claimable(who: AccountId) -> Balance {
+			HomaLite::total_claimable(&who)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
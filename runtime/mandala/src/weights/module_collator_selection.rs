@@ -53,11 +53,37 @@ impl<T: frame_system::Config> module_collator_selection::WeightInfo for WeightIn
 			.saturating_add((250_000 as Weight).saturating_mul(b as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Placeholder until benchmarked, based on `set_invulnerables`.
+	fn add_invulnerable(b: u32, ) -> Weight {
+		(23_146_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_invulnerables`.
+	fn remove_invulnerable(b: u32, ) -> Weight {
+		(23_146_000 as Weight)
+			.saturating_add((250_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn set_desired_candidates() -> Weight {
 		(20_394_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn set_candidacy_bond() -> Weight {
+		(21_014_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_candidacy_bond`.
+	fn set_max_candidacy_bond() -> Weight {
+		(21_014_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder until benchmarked, based on `set_candidacy_bond`.
+	fn set_kick_threshold() -> Weight {
 		(21_014_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}